@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use parsers::{for_each_line, ParseError, ReadError};
+use tracing::instrument;
+
+pub fn get_first_and_last_digits(row: &str) -> Option<(u32, u32)> {
+  let digits: Vec<_> = row
+    .chars()
+    .filter_map(|c| c.to_digit(10))
+    .collect();
+
+  Some((*digits.first()?, *digits.last()?))
+}
+
+// the first and last digit of each line that is not empty, as part 1 reads them
+#[instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+  return input
+    .lines()
+    .filter(|row| !row.is_empty())
+    .map(|row| {
+      get_first_and_last_digits(row)
+        .ok_or_else(|| ParseError::new(1, input, row, "a line with a digit"))
+    })
+    .collect()
+}
+
+// the sums are u64 like the ones over a reader, a long input overflows a u32
+#[instrument(level = "debug", skip_all)]
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+  return Ok(parse(input)?
+    .iter()
+    .map(|(first, last)| (first * 10 + last) as u64)
+    .sum())
+}
+
+// const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+// const DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+fn digit_map() -> HashMap<&'static str, u32> {
+  return [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+  ].iter().cloned().collect();
+}
+
+pub fn get_first_and_last_digits_improved(row: &str) -> Option<(u32, u32)> {
+  let map = digit_map();
+
+  // only start matching where a character starts, slicing anywhere else panics
+  let mut it = row.char_indices().filter_map(|(index, _)| {
+    let rest_line = &row[index..];
+
+    let next = map.keys().find(|&&key| rest_line.starts_with(key));
+
+    return next.map(|&num| map[num]);
+  });
+
+  let first = it.next()?;
+  let last = match it.next_back() {
+    Some(num) => num,
+    None => first,
+  };
+
+  return Some((first, last))
+  // return (digits.first().unwrap().clone(), digits.last().unwrap().clone())
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part2(input: &str) -> Result<u64, ParseError> {
+  return input
+    .lines()
+    // .filter(|row| { row.is_empty() })  // why is this row double &&?
+    .map(|row| {
+      if row.is_empty() {
+        return Ok(0)
+      }
+
+      let (first, last) = get_first_and_last_digits_improved(row)
+        .ok_or_else(|| ParseError::new(1, input, row, "a line with a digit or a spelled out one"))?;
+      Ok((first * 10 + last) as u64)
+    })
+    .sum()
+}
+
+// the parts over a reader, for inputs too big to hold in memory
+#[instrument(level = "debug", skip_all)]
+pub fn part1_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+  return calibrate(reader, get_first_and_last_digits, "a line with a digit");
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part2_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+  return calibrate(reader, get_first_and_last_digits_improved, "a line with a digit or a spelled out one");
+}
+
+fn calibrate(reader: impl BufRead, digits: fn(&str) -> Option<(u32, u32)>, expected: &str) -> Result<u64, ReadError> {
+  let mut sum = 0;
+  for_each_line(reader, |number, row| {
+    if row.is_empty() {
+      return Ok(())
+    }
+
+    let (first, last) = digits(row)
+      .ok_or_else(|| ParseError::new(1, row, row, expected).on_line(number))?;
+    sum += (first * 10 + last) as u64;
+    Ok(())
+  })?;
+  Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the examples from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+    const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+
+    fixtures::cases! {
+        part1 => example1, input;
+        part2 => example2, input;
+    }
+
+    #[test]
+    fn test_parse() {
+        // blank lines are left out
+        assert_eq!(parse("1abc2\n\ntreb7uchet\n").unwrap(), vec![(1, 2), (7, 7)]);
+    }
+
+    #[test]
+    fn test_part1_without_digits() {
+        let error = part1("1abc2\npqrstuvwx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.snippet, "pqrstuvwx");
+    }
+
+    #[test]
+    fn test_from_reader() {
+        assert_eq!(part1_from_reader(EXAMPLE1.as_bytes()).unwrap(), 142);
+        assert_eq!(part2_from_reader(EXAMPLE2.as_bytes()).unwrap(), 281);
+
+        // the error is the same as with the whole input in memory
+        let input = "1abc2\r\npqrstuvwx\r\n";
+        match part1_from_reader(input.as_bytes()) {
+            Err(ReadError::Parse(error)) => assert_eq!(error, part1(input).unwrap_err()),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_first_and_last_digits_improved() {
+      let expected= [
+        (2, 9),
+        (8, 3),
+        (1, 3),
+        (2, 4),
+        (4, 2),
+        (1, 4),
+        (7, 6),
+      ];
+
+      for (i, line) in EXAMPLE2.lines().enumerate() {
+        assert_eq!(Some(expected[i]), get_first_and_last_digits_improved(line))
+      }
+    }
+
+    // letters that cannot spell out a digit, so only the digits count
+    const FILLER: &str = "[abcdjklmpqyz]{0,5}";
+
+    // a calibration line with at least one digit in it
+    fn line() -> impl Strategy<Value = String> {
+        return prop::collection::vec((FILLER, 1..=9u32), 1..6)
+            .prop_flat_map(|parts| (Just(parts), FILLER))
+            .prop_map(|(parts, tail)| {
+                let mut line = String::new();
+                for (filler, digit) in parts {
+                    line.push_str(&filler);
+                    line.push_str(&digit.to_string());
+                }
+                line.push_str(&tail);
+                return line;
+            });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1_uses_first_and_last_digit(lines in prop::collection::vec(line(), 0..20)) {
+            let input = lines.join("\n");
+            let expected: u64 = lines
+                .iter()
+                .map(|line| {
+                    let digits = line.chars().filter(|c| c.is_ascii_digit()).collect::<Vec<_>>();
+                    return format!("{}{}", digits[0], digits[digits.len() - 1]).parse::<u64>().unwrap();
+                })
+                .sum();
+            prop_assert_eq!(part1(&input), Ok(expected));
+        }
+
+        #[test]
+        fn prop_part2_agrees_without_words(lines in prop::collection::vec(line(), 0..20)) {
+            let input = lines.join("\n");
+            prop_assert_eq!(part2(&input), part1(&input));
+        }
+
+        #[test]
+        fn prop_spelled_out_digits_count(line in line(), digit in 1..=9usize) {
+            let words = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+            let (_, last) = get_first_and_last_digits_improved(&line).unwrap();
+            let spelled = format!("{}{}", words[digit - 1], line);
+            prop_assert_eq!(get_first_and_last_digits_improved(&spelled), Some((digit as u32, last)));
+        }
+
+        #[test]
+        fn prop_from_reader_matches_slice(lines in prop::collection::vec(line(), 0..20), crlf: bool) {
+            let input = lines.join(if crlf { "\r\n" } else { "\n" });
+            prop_assert_eq!(part1_from_reader(input.as_bytes()).ok(), part1(&input).ok());
+            prop_assert_eq!(part2_from_reader(input.as_bytes()).ok(), part2(&input).ok());
+        }
+
+        #[test]
+        fn prop_improved_digits_never_panic(line in "\\PC*") {
+            get_first_and_last_digits_improved(&line);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// the public API of day01, as another crate sees it
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+
+#[test]
+fn test_digits() {
+    assert_eq!(
+        y2023_day01::get_first_and_last_digits("a1b2c3d4e5f"),
+        Some((1, 5))
+    );
+    assert_eq!(
+        y2023_day01::get_first_and_last_digits("treb7uchet"),
+        Some((7, 7))
+    );
+    assert_eq!(y2023_day01::get_first_and_last_digits("seven"), None);
+    assert_eq!(
+        y2023_day01::get_first_and_last_digits_improved("xtwone3four"),
+        Some((2, 4))
+    );
+}
+
+#[test]
+fn test_parts() {
+    assert_eq!(y2023_day01::part1(EXAMPLE1), Ok(142));
+    assert_eq!(y2023_day01::part2(EXAMPLE2), Ok(281));
+    assert_eq!(y2023_day01::part1("abc").unwrap_err().day, 1);
+}
@@ -0,0 +1,27 @@
+// the reader variants on inputs far bigger than what the tests could hold
+use fixtures::repeat;
+
+#[test]
+fn test_part1_from_repeated_lines() {
+    let line = "ab7cd\r\n";
+    assert_eq!(
+        y2023_day01::part1_from_reader(repeat(line, 1000)).unwrap(),
+        77 * 1000
+    );
+    assert_eq!(
+        y2023_day01::part2_from_reader(repeat("xtwone3four\n", 1000)).unwrap(),
+        24 * 1000
+    );
+}
+
+#[test]
+#[ignore = "reads 4 GiB, run with `cargo test --release -- --ignored`"]
+fn test_part1_from_4gib() {
+    let line = "abcdefghijklmnopqrstuvwxyz9abcdefghijklmnopqrstuvwxyz9abcdefghi\n";
+    let count = (4 << 30) / line.len() as u64;
+    // past what a u32 can hold
+    assert_eq!(
+        y2023_day01::part1_from_reader(repeat(line, count)).unwrap(),
+        99 * count
+    );
+}
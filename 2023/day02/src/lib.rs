@@ -0,0 +1,315 @@
+use std::fmt::{Debug, Display};
+
+use nom::{
+    branch::alt, bytes::complete::tag, character::complete::char, combinator::cut, error::context,
+    multi::separated_list1, sequence::pair,
+};
+use parsers::{labelled, number, parse_lines, record, ws, ParseError, Parsed};
+use tracing::instrument;
+
+// one handful of cubes, like "3 blue, 4 red"
+pub fn parse_cubes(input: &str) -> Parsed<'_, Cubes> {
+    let color = context(
+        "a color, one of red, blue or green",
+        alt((tag("red"), tag("blue"), tag("green"))),
+    );
+    let (input, counts) =
+        separated_list1(char(','), pair(ws(number::<u32>), cut(ws(color))))(input)?;
+    let mut cubes = Cubes {
+        red: 0,
+        blue: 0,
+        green: 0,
+    };
+
+    for (count, color) in counts {
+        match color {
+            "red" => cubes.red = count,
+            "blue" => cubes.blue = count,
+            // the parser only lets the three colors through
+            _ => cubes.green = count,
+        }
+    }
+
+    return Ok((input, cubes));
+}
+
+pub fn parse1(line: &str) -> Parsed<'_, Game> {
+    let (rest, (id, cubes)) = record(
+        labelled("Game", number::<u32>),
+        separated_list1(char(';'), parse_cubes),
+    )(line)?;
+
+    return Ok((rest, Game { id, cubes }));
+}
+
+// return ids of games that are possible
+pub fn process1(games: &[Game], cubes: &Cubes) -> Vec<u32> {
+    return games
+        .iter()
+        .filter(|&game| {
+            return game.cubes.iter().all(|cube| {
+                return cube.red <= cubes.red
+                    && cube.blue <= cubes.blue
+                    && cube.green <= cubes.green;
+            });
+        })
+        .map(|game| game.id)
+        .collect();
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    return parse_lines(2, input, parse1);
+}
+
+// the ids are u32, their sum is wider so that big ones cannot overflow it
+#[instrument(level = "debug", skip_all)]
+pub fn part1(input: &str, cubes: &Cubes) -> Result<u64, ParseError> {
+    let games = parse(input)?;
+    let ids = process1(&games, cubes);
+    return Ok(ids.iter().map(|&id| id as u64).sum());
+}
+
+#[derive(PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub cubes: Vec<Cubes>,
+}
+
+impl Debug for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut result = format!("Game {{ id: {}, cubes: [", self.id);
+        for cube in self.cubes.iter() {
+            result.push_str(&format!(
+                "Cubes {{ red: {}, blue: {}, green: {} }}, ",
+                cube.red, cube.blue, cube.green
+            ));
+        }
+        result.push_str("] }");
+        write!(f, "{}", result)
+    }
+}
+
+// the line the game was parsed from, give or take colors with no cubes
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let handfuls = self
+            .cubes
+            .iter()
+            .map(|cube| {
+                let counts = [
+                    ("red", cube.red),
+                    ("blue", cube.blue),
+                    ("green", cube.green),
+                ]
+                .iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(color, count)| format!("{} {}", count, color))
+                .collect::<Vec<_>>();
+                // a handful needs at least one color to parse again
+                if counts.is_empty() {
+                    return "0 red".to_string();
+                }
+                return counts.join(", ");
+            })
+            .collect::<Vec<_>>();
+        write!(f, "Game {}: {}", self.id, handfuls.join("; "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cubes {
+    pub red: u32,
+    pub blue: u32,
+    pub green: u32,
+}
+
+// tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the example from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    // the bag of the puzzle
+    const BAG: Cubes = Cubes {
+        red: 12,
+        blue: 14,
+        green: 13,
+    };
+
+    fixtures::cases! {
+        part1 with |input| part1(input, &BAG) => example1, input;
+    }
+
+    #[test]
+    fn test_big_ids() {
+        let input = "Game 4294967295: 1 red\nGame 4294967295: 2 blue\n";
+        assert_eq!(part1(input, &BAG), Ok(2 * u32::MAX as u64));
+    }
+
+    #[test]
+    fn test_parse1() {
+        let games = [
+            Game {
+                id: 1,
+                cubes: vec![
+                    Cubes {
+                        red: 4,
+                        blue: 3,
+                        green: 0,
+                    },
+                    Cubes {
+                        red: 1,
+                        blue: 6,
+                        green: 2,
+                    },
+                    Cubes {
+                        red: 0,
+                        blue: 0,
+                        green: 2,
+                    },
+                ],
+            },
+            Game {
+                id: 2,
+                cubes: vec![
+                    Cubes {
+                        red: 0,
+                        blue: 1,
+                        green: 2,
+                    },
+                    Cubes {
+                        red: 1,
+                        blue: 4,
+                        green: 3,
+                    },
+                    Cubes {
+                        red: 0,
+                        blue: 1,
+                        green: 1,
+                    },
+                ],
+            },
+            Game {
+                id: 3,
+                cubes: vec![
+                    Cubes {
+                        red: 20,
+                        blue: 6,
+                        green: 8,
+                    },
+                    Cubes {
+                        red: 4,
+                        blue: 5,
+                        green: 13,
+                    },
+                    Cubes {
+                        red: 1,
+                        blue: 0,
+                        green: 5,
+                    },
+                ],
+            },
+            Game {
+                id: 4,
+                cubes: vec![
+                    Cubes {
+                        red: 3,
+                        blue: 6,
+                        green: 1,
+                    },
+                    Cubes {
+                        red: 6,
+                        blue: 0,
+                        green: 3,
+                    },
+                    Cubes {
+                        red: 14,
+                        blue: 15,
+                        green: 3,
+                    },
+                ],
+            },
+            Game {
+                id: 5,
+                cubes: vec![
+                    Cubes {
+                        red: 6,
+                        blue: 1,
+                        green: 3,
+                    },
+                    Cubes {
+                        red: 1,
+                        blue: 2,
+                        green: 2,
+                    },
+                ],
+            },
+        ];
+        let output = parse(EXAMPLE1).unwrap();
+        for (i, game) in output.iter().enumerate() {
+            assert_eq!(game.id, games[i].id);
+            assert_eq!(game.cubes.len(), games[i].cubes.len());
+
+            for (j, cube) in game.cubes.iter().enumerate() {
+                assert_eq!(cube.red, games[i].cubes[j].red);
+                assert_eq!(cube.blue, games[i].cubes[j].blue);
+                assert_eq!(cube.green, games[i].cubes[j].green);
+            }
+        }
+    }
+
+    #[test]
+    fn test_process1() {
+        let output = process1(&parse(EXAMPLE1).unwrap(), &BAG);
+        assert_eq!(output, vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, 2 purple; 1 red\n";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 19));
+        assert_eq!(error.expected, "a color, one of red, blue or green");
+    }
+
+    fn cubes(max: u32) -> impl Strategy<Value = Cubes> {
+        return (0..=max, 0..=max, 0..=max).prop_map(|(red, blue, green)| Cubes {
+            red,
+            blue,
+            green,
+        });
+    }
+
+    fn game() -> impl Strategy<Value = Game> {
+        return (1..1000u32, prop::collection::vec(cubes(20), 1..6))
+            .prop_map(|(id, cubes)| Game { id, cubes });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_game_round_trip(games in prop::collection::vec(game(), 1..10)) {
+            let input = games.iter().map(|game| game.to_string()).collect::<Vec<_>>().join("\n");
+            prop_assert_eq!(parse(&input), Ok(games));
+        }
+
+        #[test]
+        fn prop_bigger_bag_allows_more_games(
+            games in prop::collection::vec(game(), 1..10),
+            bag in cubes(20),
+            extra in cubes(5),
+        ) {
+            let bigger = Cubes {
+                red: bag.red + extra.red,
+                blue: bag.blue + extra.blue,
+                green: bag.green + extra.green,
+            };
+            let possible = process1(&games, &bag);
+            let more = process1(&games, &bigger);
+            prop_assert!(possible.iter().all(|id| more.contains(id)));
+        }
+    }
+}
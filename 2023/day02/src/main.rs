@@ -0,0 +1,17 @@
+use std::error::Error;
+
+use y2023_day02::{part1, Cubes};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = inputs::open(include_bytes!("input.enc"))?;
+    let output = part1(
+        &input,
+        &Cubes {
+            red: 12,
+            blue: 14,
+            green: 13,
+        },
+    )?;
+    println!("Part 1: {}", output);
+    return Ok(());
+}
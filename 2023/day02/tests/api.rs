@@ -0,0 +1,43 @@
+// the public API of day02, as another crate sees it
+use y2023_day02::{Cubes, Game};
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+const BAG: Cubes = Cubes {
+    red: 12,
+    blue: 14,
+    green: 13,
+};
+
+#[test]
+fn test_parse1() {
+    let (rest, game) = y2023_day02::parse1("Game 7: 3 blue, 4 red; 2 green").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(
+        game,
+        Game {
+            id: 7,
+            cubes: vec![
+                Cubes {
+                    red: 4,
+                    blue: 3,
+                    green: 0
+                },
+                Cubes {
+                    red: 0,
+                    blue: 0,
+                    green: 2
+                },
+            ],
+        }
+    );
+    assert_eq!(game.to_string(), "Game 7: 4 red, 3 blue; 2 green");
+}
+
+#[test]
+fn test_parse_and_process() {
+    let games = y2023_day02::parse(EXAMPLE1).unwrap();
+    assert_eq!(games.len(), 5);
+    assert_eq!(y2023_day02::process1(&games, &BAG), [1, 2, 5]);
+    assert_eq!(y2023_day02::part1(EXAMPLE1, &BAG), Ok(8));
+}
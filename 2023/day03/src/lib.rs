@@ -0,0 +1,281 @@
+use parsers::ParseError;
+use tracing::instrument;
+
+pub mod render;
+
+// return the position of the start and length of the numbers in a line
+fn number_positions(line: &str) -> Option<Vec<(usize, usize)>> {
+    let mut number_positions: Vec<(usize, usize)> = Vec::new();
+
+    // positions are in bytes, so that slicing the line with them can never
+    // land inside a character
+    let line = line.trim().as_bytes();
+    let mut i = 0;
+    while i < line.len() {
+        if line[i].is_ascii_digit() {
+            let num = line[i..line.len()]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count();
+            number_positions.push((i, num));
+            i += num;
+        } else {
+            i += 1;
+        }
+    }
+
+    return if !number_positions.is_empty() {
+        Some(number_positions)
+    } else {
+        None
+    };
+}
+
+// return if the line has a symbol within the given position and one place to the left and right
+fn has_symbol(line: &str, (start, len): (usize, usize)) -> bool {
+    let line = line.trim().as_bytes();
+
+    // the window is cut short at either edge of the line; any byte of a non
+    // ASCII character counts as a symbol
+    let from = start.saturating_sub(1).min(line.len());
+    let to = (start + len + 1).min(line.len());
+    let window = &line[from..to];
+
+    return window.iter().any(|c| !c.is_ascii_digit() && *c != b'.');
+}
+
+// return if the number at the given position of a line has a symbol next to
+// it, on that line or the ones above and below
+fn is_part(lines: &[&str], line_number: usize, position: (usize, usize)) -> bool {
+    let above = line_number.checked_sub(1).and_then(|i| lines.get(i));
+    let below = lines.get(line_number + 1);
+
+    return [above, lines.get(line_number), below]
+        .into_iter()
+        .flatten()
+        .any(|line| has_symbol(line, position));
+}
+
+// return the "part numbers" of a line
+// a part number is the number adjacent to a "symbol"
+// a symbol is a character that is not a . or a number
+pub fn part_numbers(schematic: &str, line_number: usize) -> Result<Vec<u32>, ParseError> {
+    let lines = schematic.trim().lines().collect::<Vec<_>>();
+    let mut part_numbers: Vec<u32> = Vec::new();
+
+    // a line past the end of the schematic has no numbers, part or not
+    let line = match lines.get(line_number) {
+        Some(line) => line.trim(),
+        None => return Ok(part_numbers),
+    };
+
+    let line_numbers = number_positions(line).unwrap_or(vec![]);
+
+    for (start, len) in line_numbers {
+        let number = || {
+            return line[start..start + len].parse::<u32>().map_err(|_| {
+                ParseError::new(
+                    3,
+                    schematic,
+                    &line[start..],
+                    "a number that fits in 32 bits",
+                )
+            });
+        };
+
+        if is_part(&lines, line_number, (start, len)) {
+            part_numbers.push(number()?);
+        }
+    }
+
+    return Ok(part_numbers);
+}
+
+// the part numbers of every line of the schematic
+#[instrument(level = "debug", skip_all)]
+pub fn parse(schematic: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    return (0..schematic.trim().lines().count())
+        .map(|index| part_numbers(schematic, index))
+        .collect();
+}
+
+// the sum is wider than the numbers, a big schematic can add up past a u32
+#[instrument(level = "debug", skip_all)]
+pub fn part1(lines: &str) -> Result<u64, ParseError> {
+    return Ok(parse(lines)?.iter().flatten().map(|&n| n as u64).sum());
+}
+
+// tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the example from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    fixtures::cases! {
+        part1 => example1, input;
+    }
+
+    #[test]
+    fn test_number_positions() {
+        let expected = vec![
+            Some(vec![(0, 3), (5, 3)]),
+            None,
+            Some(vec![(2, 2), (6, 3)]),
+            None,
+            Some(vec![(0, 3)]),
+            Some(vec![(7, 2)]),
+            Some(vec![(2, 3)]),
+            Some(vec![(6, 3)]),
+            None,
+            Some(vec![(1, 3), (5, 3)]),
+        ];
+        for (i, line) in EXAMPLE1.lines().enumerate() {
+            let output = number_positions(line);
+            assert_eq!(output, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_has_symbol() {
+        let line_pos_expected = [
+            ("467..114..", (0, 4), false),
+            ("...*......", (2, 3), true),
+            ("..35..633.", (9, 3), false),
+            ("......#...", (4, 2), true),
+            ("617*......", (2, 3), true),
+            (".....+.58.", (6, 2), true),
+            ("..592.....", (4, 4), false),
+            ("......755.", (2, 2), false),
+            ("...$.*....", (0, 3), true),
+            (".664.598..", (6, 3), false),
+        ];
+
+        for (line, pos, expected) in line_pos_expected.iter() {
+            let output = has_symbol(line, *pos);
+            assert_eq!(output, *expected);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let parts = parse(EXAMPLE1).unwrap();
+        assert_eq!(parts.len(), 10);
+        assert_eq!(parts[9], vec![664, 598]);
+    }
+
+    #[test]
+    fn test_part_numbers() {
+        let expected = [
+            vec![467],
+            vec![],
+            vec![35, 633],
+            vec![],
+            vec![617],
+            vec![],
+            vec![592],
+            vec![755],
+            vec![],
+            vec![664, 598],
+        ];
+
+        for (i, _line) in EXAMPLE1.lines().enumerate() {
+            let output = part_numbers(EXAMPLE1, i).unwrap();
+            assert_eq!(output, expected[i]);
+        }
+
+        // nothing past the last line, nor in an empty schematic
+        assert_eq!(part_numbers(EXAMPLE1, expected.len()), Ok(vec![]));
+        assert_eq!(part_numbers("", 0), Ok(vec![]));
+    }
+
+    // rows of dots, digits and symbols; narrow enough that a number fits a u32
+    fn schematic() -> impl Strategy<Value = Vec<String>> {
+        let cell = prop_oneof![
+            6 => Just('.'),
+            3 => prop::char::range('0', '9'),
+            1 => prop::sample::select(vec!['*', '#', '+', '$', '/', '@', '=', '%', '-', '&']),
+        ];
+        return (1..9usize, 1..9usize).prop_flat_map(move |(width, height)| {
+            prop::collection::vec(
+                prop::collection::vec(cell.clone(), width)
+                    .prop_map(|row| row.into_iter().collect()),
+                height,
+            )
+        });
+    }
+
+    // the sum of part numbers, checking all eight neighbours of every digit
+    fn reference(rows: &[String]) -> u64 {
+        let grid = rows
+            .iter()
+            .map(|row| row.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let is_symbol = |y: i64, x: i64| {
+            if y < 0 || x < 0 || y as usize >= grid.len() || x as usize >= grid[y as usize].len() {
+                return false;
+            }
+            let c = grid[y as usize][x as usize];
+            return !c.is_ascii_digit() && c != '.';
+        };
+
+        let mut sum: u64 = 0;
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                if !row[x].is_ascii_digit() {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    x += 1;
+                }
+                let adjacent = (start as i64 - 1..=x as i64)
+                    .any(|nx| (-1..=1).any(|dy| is_symbol(y as i64 + dy, nx)));
+                if adjacent {
+                    sum += row[start..x]
+                        .iter()
+                        .collect::<String>()
+                        .parse::<u64>()
+                        .unwrap();
+                }
+            }
+        }
+
+        return sum;
+    }
+
+    #[test]
+    fn test_has_symbol_edges() {
+        // a number running up to the right edge, or spanning the whole line
+        assert!(has_symbol("....*", (3, 2)));
+        assert!(!has_symbol(".....", (3, 2)));
+        assert!(has_symbol("*....", (0, 5)));
+        assert!(has_symbol("*....", (0, 2)));
+        assert!(!has_symbol("..*..", (0, 1)));
+    }
+
+    #[test]
+    fn test_part1_unusual_input() {
+        // characters wider than a byte are symbols like any other
+        assert_eq!(part1("12é..\n..€34"), Ok(46));
+        let error = part1("..*\n.12345678901").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a number that fits in 32 bits");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1_matches_reference(rows in schematic()) {
+            prop_assert_eq!(part1(&rows.join("\n")), Ok(reference(&rows)));
+        }
+
+        #[test]
+        fn prop_part1_ignores_line_endings(rows in schematic()) {
+            prop_assert_eq!(part1(&rows.join("\r\n")), part1(&rows.join("\n")));
+        }
+    }
+}
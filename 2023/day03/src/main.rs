@@ -0,0 +1,9 @@
+use std::error::Error;
+
+use y2023_day03::part1;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = inputs::open(include_bytes!("input.enc"))?;
+    println!("Part 1: {}", part1(&input)?);
+    return Ok(());
+}
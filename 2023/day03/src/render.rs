@@ -0,0 +1,245 @@
+// draws the schematic with the decisions `part_numbers` makes, to check them
+// by eye: in the terminal with ANSI colours, or as a standalone HTML page
+use super::{is_part, number_positions};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Empty,
+    Symbol,
+    // a `*` next to exactly two numbers, counted in reading order
+    Gear(usize),
+    // a number without a symbol around it
+    Number,
+    // a part number, with the gear it belongs to if any
+    Part(Option<usize>),
+}
+
+// every character of the schematic, by line, with what it turned out to be
+pub fn classify(schematic: &str) -> Vec<Vec<(char, Kind)>> {
+    let lines = schematic
+        .trim()
+        .lines()
+        .map(|line| line.trim())
+        .collect::<Vec<_>>();
+
+    // kinds are indexed by byte, like the positions of the numbers
+    let mut kinds = lines
+        .iter()
+        .map(|line| {
+            line.bytes()
+                .map(|c| match c {
+                    b'.' => Kind::Empty,
+                    c if c.is_ascii_digit() => Kind::Number,
+                    _ => Kind::Symbol,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut numbers = Vec::new();
+    for (y, line) in lines.iter().enumerate() {
+        for (start, len) in number_positions(line).unwrap_or(vec![]) {
+            if is_part(&lines, y, (start, len)) {
+                kinds[y][start..start + len].fill(Kind::Part(None));
+            }
+            numbers.push((y, start, len));
+        }
+    }
+
+    let mut gears = 0;
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.bytes().enumerate() {
+            if c != b'*' {
+                continue;
+            }
+            let adjacent = numbers
+                .iter()
+                .filter(|&&(ny, start, len)| {
+                    ny.abs_diff(y) <= 1 && x + 1 >= start && x <= start + len
+                })
+                .collect::<Vec<_>>();
+            if adjacent.len() != 2 {
+                continue;
+            }
+
+            kinds[y][x] = Kind::Gear(gears);
+            // a number between two gears stays with the first one
+            for &&(ny, start, len) in &adjacent {
+                for kind in &mut kinds[ny][start..start + len] {
+                    if *kind == Kind::Part(None) {
+                        *kind = Kind::Part(Some(gears));
+                    }
+                }
+            }
+            gears += 1;
+        }
+    }
+
+    return lines
+        .iter()
+        .zip(kinds)
+        .map(|(line, kinds)| line.char_indices().map(|(i, c)| (c, kinds[i])).collect())
+        .collect();
+}
+
+// the cells of a line, merged into runs of the same kind
+fn runs(line: &[(char, Kind)]) -> Vec<(Kind, String)> {
+    let mut runs: Vec<(Kind, String)> = Vec::new();
+    for &(c, kind) in line {
+        match runs.last_mut() {
+            Some((last, text)) if *last == kind => text.push(c),
+            _ => runs.push((kind, c.to_string())),
+        }
+    }
+
+    return runs;
+}
+
+// backgrounds the gears and their numbers cycle through, so neighbours differ
+const ANSI_GEARS: [u8; 4] = [44, 45, 46, 43];
+
+pub fn ansi(schematic: &str) -> String {
+    let mut out = String::new();
+    for line in classify(schematic) {
+        for (kind, text) in runs(&line) {
+            let style = match kind {
+                Kind::Empty => "2".to_string(),
+                Kind::Symbol => "1;33".to_string(),
+                Kind::Number => "31".to_string(),
+                Kind::Part(None) => "32".to_string(),
+                Kind::Part(Some(gear)) => format!("30;{}", ANSI_GEARS[gear % ANSI_GEARS.len()]),
+                Kind::Gear(gear) => format!("1;30;{}", ANSI_GEARS[gear % ANSI_GEARS.len()]),
+            };
+            out.push_str(&format!("\x1b[{}m{}\x1b[0m", style, text));
+        }
+        out.push('\n');
+    }
+
+    return out;
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>day03 schematic</title>
+<style>
+body { background: #1e1e1e; color: #ddd; font-family: sans-serif; }
+pre { font-size: 14px; line-height: 1.2; }
+.empty { color: #555; }
+.symbol { color: #fc3; font-weight: bold; }
+.number { color: #f66; }
+.part { color: #6d6; }
+.gear { font-weight: bold; }
+.g0 { background: #258; color: #fff; }
+.g1 { background: #747; color: #fff; }
+.g2 { background: #277; color: #fff; }
+.g3 { background: #752; color: #fff; }
+</style>
+</head>
+<body>
+<p>
+<span class="part">part number</span>
+<span class="number">not a part number</span>
+<span class="symbol">symbol</span>
+<span class="gear g0">gear</span> and its two numbers share a background
+</p>
+<pre>
+"#;
+
+pub fn html(schematic: &str) -> String {
+    let mut out = HTML_HEAD.to_string();
+    for line in classify(schematic) {
+        for (kind, text) in runs(&line) {
+            let class = match kind {
+                Kind::Empty => "empty".to_string(),
+                Kind::Symbol => "symbol".to_string(),
+                Kind::Number => "number".to_string(),
+                Kind::Part(None) => "part".to_string(),
+                Kind::Part(Some(gear)) => format!("part g{}", gear % 4),
+                Kind::Gear(gear) => format!("gear g{}", gear % 4),
+            };
+            let title = match kind {
+                Kind::Part(Some(gear)) | Kind::Gear(gear) => format!(" title=\"gear {}\"", gear),
+                _ => String::new(),
+            };
+            out.push_str(&format!(
+                "<span class=\"{}\"{}>{}</span>",
+                class,
+                title,
+                escape(&text)
+            ));
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+
+    return out;
+}
+
+fn escape(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    fn kinds(line: &[(char, Kind)]) -> Vec<Kind> {
+        return line.iter().map(|&(_, kind)| kind).collect();
+    }
+
+    #[test]
+    fn test_classify() {
+        let grid = classify(EXAMPLE1);
+        assert_eq!(grid.len(), 10);
+
+        // 467 and 35 share the first gear, 114 is not a part
+        assert_eq!(kinds(&grid[0])[0..3], [Kind::Part(Some(0)); 3]);
+        assert_eq!(kinds(&grid[0])[5..8], [Kind::Number; 3]);
+        assert_eq!(grid[1][3], ('*', Kind::Gear(0)));
+        assert_eq!(kinds(&grid[2])[2..4], [Kind::Part(Some(0)); 2]);
+        assert_eq!(kinds(&grid[2])[6..9], [Kind::Part(None); 3]);
+
+        // a `*` next to only one number is just a symbol
+        assert_eq!(grid[4][3], ('*', Kind::Symbol));
+        assert_eq!(kinds(&grid[5])[7..9], [Kind::Number; 2]);
+
+        assert_eq!(grid[8][5], ('*', Kind::Gear(1)));
+        assert_eq!(kinds(&grid[7])[6..9], [Kind::Part(Some(1)); 3]);
+        assert_eq!(kinds(&grid[9])[5..8], [Kind::Part(Some(1)); 3]);
+    }
+
+    #[test]
+    fn test_classify_matches_part1() {
+        // the numbers drawn as parts add up to the answer of part 1
+        let sum = classify(EXAMPLE1)
+            .iter()
+            .flat_map(|line| runs(line))
+            .filter(|(kind, _)| matches!(kind, Kind::Part(_)))
+            .map(|(_, text)| text.parse::<u64>().unwrap())
+            .sum::<u64>();
+        assert_eq!(Ok(sum), crate::part1(EXAMPLE1));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let out = ansi("467..114..\n...*......\n..35..633.");
+        assert!(out.starts_with("\x1b[30;44m467\x1b[0m\x1b[2m..\x1b[0m\x1b[31m114\x1b[0m"));
+        assert!(out.contains("\x1b[1;30;44m*\x1b[0m"));
+        assert_eq!(out.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_html() {
+        let out = html("12&..\n.....");
+        assert!(out.starts_with("<!DOCTYPE html>"));
+        assert!(out.contains("<span class=\"part\">12</span><span class=\"symbol\">&amp;</span>"));
+        assert!(out.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,19 @@
+// the public API of day03, as another crate sees it
+use y2023_day03::render::{classify, Kind};
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+#[test]
+fn test_part_numbers() {
+    assert_eq!(y2023_day03::part_numbers(EXAMPLE1, 0), Ok(vec![467]));
+    assert_eq!(y2023_day03::part_numbers(EXAMPLE1, 2), Ok(vec![35, 633]));
+    assert_eq!(y2023_day03::part_numbers(EXAMPLE1, 5), Ok(vec![]));
+    assert_eq!(y2023_day03::part1(EXAMPLE1), Ok(4361));
+}
+
+#[test]
+fn test_classify() {
+    let grid = classify(EXAMPLE1);
+    assert_eq!(grid[1][3], ('*', Kind::Gear(0)));
+    assert_eq!(grid[0][5], ('1', Kind::Number));
+}
@@ -0,0 +1,262 @@
+use std::io::BufRead;
+
+use nom::{character::complete::char, sequence::separated_pair};
+use parsers::{
+    for_each_line, labelled, number, numbers, parse_line, parse_lines, record, ParseError, Parsed,
+    ReadError,
+};
+use tracing::{debug, instrument};
+
+pub fn get_winning_numbers(card: &Card) -> Vec<&u32> {
+    return card
+        .your_numbers
+        .iter()
+        .filter(|&n| card.winning_numbers.contains(n))
+        .collect::<Vec<_>>();
+}
+
+pub fn get_card(line: &str) -> Parsed<'_, Card> {
+    debug!(line, "card");
+    let (rest, (id, (winning_numbers, your_numbers))) = record(
+        labelled("Card", number::<u32>),
+        separated_pair(numbers, char('|'), numbers),
+    )(line)?;
+
+    return Ok((
+        rest,
+        Card {
+            id,
+            winning_numbers,
+            your_numbers,
+        },
+    ));
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse(lines: &str) -> Result<Vec<Card>, ParseError> {
+    return parse_lines(4, lines, get_card);
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part1(lines: &str) -> Result<u64, ParseError> {
+    let cards = parse(lines)?;
+    let numbered = lines
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let mut sum = 0;
+    for ((index, line), card) in numbered.zip(&cards) {
+        sum = add_points(sum, card, line, index + 1)?;
+    }
+
+    return Ok(sum);
+}
+
+// part1 over a reader, a card at a time
+#[instrument(level = "debug", skip_all)]
+pub fn part1_from_reader(reader: impl BufRead) -> Result<u64, ReadError> {
+    let mut sum = 0;
+    for_each_line(reader, |number, line| {
+        if !line.trim().is_empty() {
+            let card = parse_line(4, number, line, get_card)?;
+            sum = add_points(sum, &card, line, number)?;
+        }
+        return Ok(());
+    })?;
+
+    return Ok(sum);
+}
+
+// one point for the first winning number, doubled for every other one; None
+// past 64 of them, the points no longer fit in a u64
+fn points(card: &Card) -> Option<u64> {
+    return match get_winning_numbers(card).len() {
+        0 => Some(0),
+        n => u32::try_from(n - 1)
+            .ok()
+            .and_then(|shift| 1u64.checked_shl(shift)),
+    };
+}
+
+// `sum` with the points of `card` added, which is on line `number`
+fn add_points(sum: u64, card: &Card, line: &str, number: usize) -> Result<u64, ParseError> {
+    return points(card)
+        .and_then(|points| sum.checked_add(points))
+        .ok_or_else(|| {
+            ParseError::new(
+                4,
+                line,
+                line.trim_start(),
+                "cards worth less than 2^64 points",
+            )
+            .on_line(number)
+        });
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: u32,
+    pub winning_numbers: Vec<u32>,
+    pub your_numbers: Vec<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the example from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    fixtures::cases! {
+        part1 => example1, input;
+    }
+
+    #[test]
+    fn test_get_card() {
+        let expected = [
+            Card {
+                id: 1,
+                your_numbers: vec![83, 86, 6, 31, 17, 9, 48, 53],
+                winning_numbers: vec![41, 48, 83, 86, 17],
+            },
+            Card {
+                id: 2,
+                your_numbers: vec![61, 30, 68, 82, 17, 32, 24, 19],
+                winning_numbers: vec![13, 32, 20, 16, 61],
+            },
+            Card {
+                id: 3,
+                your_numbers: vec![69, 82, 63, 72, 16, 21, 14, 1],
+                winning_numbers: vec![1, 21, 53, 59, 44],
+            },
+            Card {
+                id: 4,
+                your_numbers: vec![59, 84, 76, 51, 58, 5, 54, 83],
+                winning_numbers: vec![41, 92, 73, 84, 69],
+            },
+            Card {
+                id: 5,
+                your_numbers: vec![88, 30, 70, 12, 93, 22, 82, 36],
+                winning_numbers: vec![87, 83, 26, 28, 32],
+            },
+            Card {
+                id: 6,
+                your_numbers: vec![74, 77, 10, 23, 35, 67, 36, 11],
+                winning_numbers: vec![31, 18, 13, 56, 72],
+            },
+        ];
+
+        for (i, line) in EXAMPLE1.lines().enumerate() {
+            let (_, output) = get_card(line).unwrap();
+            assert_eq!(output, expected[i]);
+        }
+    }
+
+    #[test]
+    fn test_part1_from_reader() {
+        assert_eq!(part1_from_reader(EXAMPLE1.as_bytes()).unwrap(), 13);
+
+        // the error is the same as with the whole input in memory
+        let input = "Card 1: 41 48 | 83 86\n\nCard 2: 13 32 20 - 61 30\n";
+        match part1_from_reader(input.as_bytes()) {
+            Err(ReadError::Parse(error)) => assert_eq!(error, parse(input).unwrap_err()),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_too_many_points() {
+        let numbers = (1..=65)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let card = format!("Card 1: {} | {}", numbers, numbers);
+        let error = part1(&format!("Card 1: 1 | 2\n{}\n", card)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "cards worth less than 2^64 points");
+        let error = part1_from_reader(format!("Card 1: 1 | 2\n{}\n", card).as_bytes());
+        assert!(matches!(error, Err(ReadError::Parse(e)) if e.line == 2));
+
+        // 64 matches are still worth a u64, twice as many points are not
+        let card = card.replace(" 65", "");
+        assert_eq!(part1(&card), Ok(1 << 63));
+        assert!(part1(&format!("{}\n{}", card, card)).is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 20 - 61 30\n";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 18));
+        assert_eq!(error.expected, "'|'");
+    }
+
+    #[test]
+    fn test_get_card_spacing() {
+        let input = "\tCard  7:  1 2   3 | 4 5   ";
+        let expected = Card {
+            id: 7,
+            winning_numbers: vec![1, 2, 3],
+            your_numbers: vec![4, 5],
+        };
+        assert_eq!(get_card(input), Ok(("", expected)));
+    }
+
+    fn card() -> impl Strategy<Value = Card> {
+        return (
+            1..300u32,
+            prop::collection::vec(0..100u32, 0..10),
+            prop::collection::vec(0..100u32, 0..25),
+        )
+            .prop_map(|(id, winning_numbers, your_numbers)| Card {
+                id,
+                winning_numbers,
+                your_numbers,
+            });
+    }
+
+    // a card line, with the numbers padded by 1 to 3 spaces like the real input
+    fn format_card(card: &Card, padding: &[usize]) -> String {
+        let mut pad = padding.iter().cycle();
+        let mut join = |numbers: &[u32]| {
+            return numbers
+                .iter()
+                .map(|n| format!("{}{}", " ".repeat(*pad.next().unwrap()), n))
+                .collect::<String>();
+        };
+        let winning = join(&card.winning_numbers);
+        let yours = join(&card.your_numbers);
+        return format!("Card {:>3}:{} |{}", card.id, winning, yours);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_card_round_trip(
+            cards in prop::collection::vec(card(), 1..10),
+            padding in prop::collection::vec(1..=3usize, 1..8),
+        ) {
+            let input = cards.iter().map(|c| format_card(c, &padding)).collect::<Vec<_>>().join("\n");
+            prop_assert_eq!(parse(&input), Ok(cards));
+        }
+
+        #[test]
+        fn prop_points_double_per_match(cards in prop::collection::vec(card(), 1..10)) {
+            let input = cards.iter().map(|c| format_card(c, &[1])).collect::<Vec<_>>().join("\n");
+            let expected: u64 = cards
+                .iter()
+                .map(|card| {
+                    let matches = card
+                        .your_numbers
+                        .iter()
+                        .filter(|n| card.winning_numbers.contains(n))
+                        .count();
+                    return if matches == 0 { 0 } else { 1 << (matches - 1) };
+                })
+                .sum();
+            prop_assert_eq!(part1(&input), Ok(expected));
+            prop_assert_eq!(part1_from_reader(input.as_bytes()).ok(), Some(expected));
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// the public API of day04, as another crate sees it
+use y2023_day04::Card;
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+#[test]
+fn test_get_card() {
+    let (_, card) = y2023_day04::get_card("Card 3: 1 21 | 21 5 1").unwrap();
+    assert_eq!(
+        card,
+        Card {
+            id: 3,
+            winning_numbers: vec![1, 21],
+            your_numbers: vec![21, 5, 1],
+        }
+    );
+    assert_eq!(y2023_day04::get_winning_numbers(&card), [&21, &1]);
+}
+
+#[test]
+fn test_parse_and_part1() {
+    let cards = y2023_day04::parse(EXAMPLE1).unwrap();
+    assert_eq!(
+        cards.iter().map(|card| card.id).collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(y2023_day04::part1(EXAMPLE1), Ok(13));
+}
@@ -0,0 +1,23 @@
+// the reader variant on inputs far bigger than what the tests could hold
+use fixtures::repeat;
+
+// four of the winning numbers come up, eight points per card
+const CARD: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n";
+
+#[test]
+fn test_part1_from_repeated_cards() {
+    assert_eq!(
+        y2023_day04::part1_from_reader(repeat(CARD, 1000)).unwrap(),
+        8 * 1000
+    );
+}
+
+#[test]
+#[ignore = "reads 2 GiB, run with `cargo test --release -- --ignored`"]
+fn test_part1_from_2gib() {
+    let count = (2 << 30) / CARD.len() as u64;
+    assert_eq!(
+        y2023_day04::part1_from_reader(repeat(CARD, count)).unwrap(),
+        8 * count
+    );
+}
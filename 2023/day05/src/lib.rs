@@ -0,0 +1,608 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, line_ending},
+    combinator::cut,
+    error::{context, ContextError, ErrorKind, ParseError as _, VerboseError},
+    multi::{many0, separated_list0},
+    sequence::{pair, separated_pair, terminated, tuple},
+};
+use parsers::{
+    blank_lines, labelled, labelled_numbers, line_end, number, parse_all, sections, ws, ParseError,
+    Parsed,
+};
+use progress::Progress;
+use rayon::prelude::*;
+use tracing::{instrument, trace};
+
+pub mod render;
+pub mod repl;
+
+// the first seed of a range in part 2, and how many seeds it holds
+pub type SeedRange = (u64, u64);
+
+// `len` values from `src` on map to as many values from `dst` on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub src: u64,
+    pub dst: u64,
+    pub len: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map<'a> {
+    pub from: &'a str,
+    pub to: &'a str,
+    pub map: Vec<Range>,
+}
+
+fn parse_seeds(input: &str) -> Parsed<'_, Vec<u64>> {
+    return labelled_numbers("seeds:")(input);
+}
+
+// a range whose end does not fit in a u64, at `input`; its values could not
+// all be looked up or mapped
+fn past_the_end(input: &str) -> nom::Err<VerboseError<&str>> {
+    return nom::Err::Failure(VerboseError::add_context(
+        input,
+        "a range that ends before 2^64",
+        VerboseError::from_error_kind(input, ErrorKind::Verify),
+    ));
+}
+
+fn parse_seed_range(input: &str) -> Parsed<'_, SeedRange> {
+    // once a range has started, its length has to follow
+    let (rest, (start, len)) = pair(ws(number::<u64>), cut(ws(number)))(input)?;
+    if start.checked_add(len).is_none() {
+        return Err(past_the_end(input));
+    }
+
+    return Ok((rest, (start, len)));
+}
+
+fn parse_seeds_ranges(input: &str) -> Parsed<'_, Vec<SeedRange>> {
+    return labelled("seeds:", many0(parse_seed_range))(input);
+}
+
+fn parse_mapping(input: &str) -> Parsed<'_, (u64, u64, u64)> {
+    // a line that starts with a number has to be a whole mapping, so that a
+    // typo is reported where it is rather than at the end of the map
+    let (rest, (dst, (src, len))) = pair(
+        ws(number::<u64>),
+        cut(tuple((ws(number::<u64>), ws(number)))),
+    )(input)?;
+    if src.checked_add(len).is_none() || dst.checked_add(len).is_none() {
+        return Err(past_the_end(input));
+    }
+
+    return Ok((rest, (src, dst, len)));
+}
+
+// a map has the following format:
+// <from>-to-<to> map:
+// followed by a list of <to> <from> <length> lines
+// which specify the start destination, start source and length of the keys
+// any key which is not specified is assumed to be equal in both collections
+pub fn parse_map(input: &str) -> Parsed<'_, Map<'_>> {
+    let (input, (from, to)) = terminated(
+        context(
+            "a map header like \"seed-to-soil map:\"",
+            ws(separated_pair(alpha1, tag("-to-"), alpha1)),
+        ),
+        pair(tag("map:"), line_end),
+    )(input)?;
+    let (input, mappings) = separated_list0(line_ending, parse_mapping)(input)?;
+
+    let map = mappings
+        .iter()
+        .map(|(src, dst, len)| Range {
+            src: *src,
+            dst: *dst,
+            len: *len,
+        })
+        .collect();
+
+    return Ok((input, Map { from, to, map }));
+}
+
+/// Returns the mapped value for a given key from the provided map.
+/// If the key is not found in the map, it returns the key itself.
+///
+/// # Arguments
+///
+/// * `map` - A reference to a Map struct which contains the mapping.
+/// * `from` - The key for which to find the mapped value.
+///
+/// # Returns
+///
+/// * The mapped value if the key is found in the map, otherwise the key itself.
+///
+/// # Examples
+///
+/// ```ignore
+/// let mut map = Map::new();
+/// map.map.insert(1, 2);
+/// assert_eq!(get_mapping(&map, 1), 2);
+/// assert_eq!(get_mapping(&map, 3), 3);
+/// ```
+pub fn get_mapping(map: &Map, from: u64) -> u64 {
+    // the parser keeps `dst + len` within a u64, and so the mapped value
+    let found = map
+        .map
+        .iter()
+        .find(|&r| r.src <= from && from - r.src < r.len);
+
+    match found {
+        Some(r) => r.dst + (from - r.src),
+        None => from,
+    }
+}
+
+fn parse_maps(input: &str) -> Parsed<'_, Vec<Map<'_>>> {
+    let (input, maps) = sections(parse_map)(input)?;
+
+    return Ok((input, maps));
+}
+
+// the value `value` of `from` becomes in `to`, if the maps lead from one to
+// the other; a miss is only traced, it happens once per seed and the parts
+// report a missing route once, before looking any seed up
+pub fn find_dest(from: &str, value: u64, to: &str, maps: &[Map]) -> Option<u64> {
+    let mut i = 0;
+    let Some(mut cur_map) = maps.iter().find(|&m| m.from == from) else {
+        trace!(from, value, to, "no map starts there");
+        return None;
+    };
+    let mut cur_value = get_mapping(cur_map, value);
+    // a single map can already lead to the destination
+    if cur_map.to == to {
+        return Some(cur_value);
+    }
+
+    while i < maps.len() {
+        let Some(next) = maps.iter().find(|&m| m.from == cur_map.to) else {
+            break;
+        };
+        cur_map = next;
+        cur_value = get_mapping(cur_map, cur_value);
+
+        if cur_map.to == to {
+            trace!(from, value, to, found = cur_value, steps = i);
+            return Some(cur_value);
+        }
+
+        i += 1;
+    }
+
+    trace!(from, value, to, "no map leads there");
+    return None;
+}
+
+fn get_seeds_and_maps(input: &str) -> Parsed<'_, (Vec<u64>, Vec<Map<'_>>)> {
+    let (input, seeds) = parse_seeds(input)?;
+    let (input, _) = blank_lines(input)?;
+    let (input, maps) = parse_maps(input)?;
+
+    return Ok((input, (seeds, maps)));
+}
+
+fn get_seed_ranges_and_maps(input: &str) -> Parsed<'_, (Vec<SeedRange>, Vec<Map<'_>>)> {
+    let (input, seeds) = parse_seeds_ranges(input)?;
+    let (input, _) = blank_lines(input)?;
+    let (input, maps) = parse_maps(input)?;
+
+    return Ok((input, (seeds, maps)));
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<Map<'_>>), ParseError> {
+    return parse_all(5, input, get_seeds_and_maps);
+}
+
+// the seeds line read as ranges, like part 2 does
+#[instrument(level = "debug", skip_all)]
+pub fn parse_ranges(input: &str) -> Result<(Vec<SeedRange>, Vec<Map<'_>>), ParseError> {
+    return parse_all(5, input, get_seed_ranges_and_maps);
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+    return part1_between(input, "seed", "location")?.ok_or_else(|| no_seeds(input));
+}
+
+// there is no lowest value of anything without seeds to start from
+fn no_seeds(input: &str) -> ParseError {
+    let at = input
+        .find("seeds:")
+        .map_or(input, |i| &input[i + "seeds:".len()..]);
+    return ParseError::new(5, input, at, "at least one seed");
+}
+
+// fails unless a chain of maps leads from `from` to `to`, the way `find_dest`
+// follows them, naming the categories the almanac has
+fn check_route(input: &str, maps: &[Map], from: &str, to: &str) -> Result<(), ParseError> {
+    let mut current = maps.iter().find(|m| m.from == from);
+    for _ in 0..maps.len() {
+        match current {
+            Some(map) if map.to == to => return Ok(()),
+            Some(map) => current = maps.iter().find(|m| m.from == map.to),
+            None => break,
+        }
+    }
+
+    let mut categories = Vec::new();
+    for name in maps.iter().flat_map(|m| [m.from, m.to]) {
+        if !categories.contains(&name) {
+            categories.push(name);
+        }
+    }
+    return Err(ParseError::new(
+        5,
+        input,
+        &input[input.trim_end().len()..],
+        &format!(
+            "maps leading from {} to {}, the categories are {}",
+            from,
+            to,
+            categories.join(", ")
+        ),
+    ));
+}
+
+// part1 with the seeds taken as values of `from`, and the lowest value of
+// `to` they end up at; None if there are no seeds, and an error if no maps
+// lead from one to the other
+#[instrument(level = "debug", skip_all)]
+pub fn part1_between(input: &str, from: &str, to: &str) -> Result<Option<u64>, ParseError> {
+    let (seeds, maps) = parse(input)?;
+    check_route(input, &maps, from, to)?;
+
+    return Ok(seeds
+        .iter()
+        .filter_map(|s| find_dest(from, *s, to, &maps))
+        .min());
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part2(input: &str) -> Result<u64, ParseError> {
+    return part2_with(input, "seed", "location", &Progress::new())?.ok_or_else(|| no_seeds(input));
+}
+
+// seeds are looked up this many at a time between two checks of the progress
+const CHUNK: u64 = 1 << 16;
+
+// part2 between any two categories like `part1_between`, counting the seeds
+// done into `progress` and stopping early once it is cancelled, with the
+// lowest value found so far
+#[instrument(level = "debug", skip_all)]
+pub fn part2_with(
+    input: &str,
+    from: &str,
+    to: &str,
+    progress: &Progress,
+) -> Result<Option<u64>, ParseError> {
+    let (seeds_ranges, maps) = parse_ranges(input)?;
+    check_route(input, &maps, from, to)?;
+    progress.set_total(seeds_ranges.iter().map(|&(_, l)| l).sum());
+
+    let chunks = seeds_ranges
+        .iter()
+        .flat_map(|&(s, l)| {
+            (s..s + l)
+                .step_by(CHUNK as usize)
+                .map(move |c| (c, (s + l).min(c.saturating_add(CHUNK))))
+        })
+        .collect::<Vec<_>>();
+
+    return Ok(chunks
+        .par_iter()
+        .filter_map(|&(start, end)| {
+            if progress.is_cancelled() {
+                return None;
+            }
+            let min = (start..end)
+                .filter_map(|val| find_dest(from, val, to, &maps))
+                .min();
+            progress.advance(end - start);
+            return min;
+        })
+        .min());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the examples from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+    const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+
+    // part 2 tries every seed, far too many of them in the real input for
+    // a debug build
+    fixtures::cases! {
+        part1 => example1, input;
+        part2 => example2;
+    }
+
+    #[test]
+    fn test_parse_seeds() {
+        let seeds = "seeds: 79 14 55 13";
+        let expected: Vec<u64> = vec![79, 14, 55, 13];
+        match parse_seeds(seeds) {
+            Ok((_, result)) => assert_eq!(result, expected),
+            Err(_) => panic!("parse_seeds failed"),
+        }
+    }
+
+    #[test]
+    fn test_get_mapping() {
+        let map = "seed-to-soil map:
+        50 98 2
+        52 50 48";
+        // The entire list of seed numbers and their corresponding soil numbers looks like this:
+        // seed  soil
+        // 0     0
+        // 1     1
+        // ...   ...
+        // 48    48
+        // 49    49
+        // 50    52
+        // 51    53
+        // ...   ...
+        // 96    98
+        // 97    99
+        // 98    50
+        // 99    51
+        let seed_to_soil = vec![
+            (0, 0),
+            (1, 1),
+            (48, 48),
+            (49, 49),
+            (50, 52),
+            (51, 53),
+            (96, 98),
+            (97, 99),
+            (98, 50),
+            (99, 51),
+        ];
+
+        match parse_map(map) {
+            Ok((_, result)) => {
+                assert_eq!(result.from, "seed");
+                assert_eq!(result.to, "soil");
+                assert_eq!(result.map.len(), 2);
+                for (seed, soil) in seed_to_soil {
+                    assert_eq!(get_mapping(&result, seed), soil);
+                }
+            }
+            Err(_) => panic!("parse_map failed"),
+        }
+    }
+
+    #[test]
+    fn test_find_dest() {
+        let (_, (seeds, maps)) = get_seeds_and_maps(EXAMPLE1).unwrap();
+        let val = find_dest("seed", seeds[0], "location", &maps).unwrap();
+        assert_eq!(val, 82);
+
+        // backwards, or from or to a category that is not there
+        assert_eq!(find_dest("location", 82, "seed", &maps), None);
+        assert_eq!(find_dest("dirt", 82, "location", &maps), None);
+        assert_eq!(find_dest("seed", 79, "dirt", &maps), None);
+    }
+
+    #[test]
+    fn test_between() {
+        // the soil of seed 13 is the lowest, and its light is 34
+        assert_eq!(part1_between(EXAMPLE1, "seed", "soil"), Ok(Some(13)));
+        assert_eq!(part1_between(EXAMPLE1, "seed", "light"), Ok(Some(34)));
+        // the seeds read as soil numbers
+        assert_eq!(part1_between(EXAMPLE1, "soil", "fertilizer"), Ok(Some(52)));
+
+        // the categories are checked before any seed is looked up
+        let expected = "maps leading from seed to locaton, the categories are seed, soil, \
+            fertilizer, water, light, temperature, humidity, location";
+        let error = part1_between(EXAMPLE1, "seed", "locaton").unwrap_err();
+        assert_eq!(error.expected, expected);
+        let error = part2_with(EXAMPLE2, "seed", "locaton", &Progress::new()).unwrap_err();
+        assert_eq!(error.expected, expected);
+        assert!(part1_between(EXAMPLE1, "location", "seed").is_err());
+
+        let progress = Progress::new();
+        assert_eq!(
+            part2_with(EXAMPLE2, "seed", "soil", &progress),
+            Ok(Some(57))
+        );
+    }
+
+    #[test]
+    fn test_ranges_past_the_end() {
+        let input = EXAMPLE1.replacen("52 50 48", "52 18446744073709551610 48", 1);
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.expected, "a range that ends before 2^64");
+        let input = EXAMPLE1.replacen("52 50 48", "18446744073709551610 50 48", 1);
+        assert_eq!(parse(&input).unwrap_err().line, 5);
+
+        let input = EXAMPLE2.replacen("55 13", "18446744073709551610 13", 1);
+        let error = parse_ranges(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 14));
+        assert_eq!(error.expected, "a range that ends before 2^64");
+
+        // up to the last value is fine
+        let input =
+            "seeds: 18446744073709551614 1\n\nseed-to-location map:\n0 18446744073709551614 1\n";
+        assert_eq!(part1(input), Ok(0));
+        assert_eq!(part2(input), Ok(0));
+    }
+
+    #[test]
+    fn test_nothing_found() {
+        let input = "seeds:\n\nseed-to-location map:\n1 2 3\n";
+        let error = part1(input).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.expected, "at least one seed");
+        assert_eq!(part2(input).unwrap_err(), error);
+
+        // seeds, but no way to their locations
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n";
+        let error = part1(input).unwrap_err();
+        assert_eq!((error.line, error.column), (4, 6));
+        assert_eq!(
+            error.expected,
+            "maps leading from seed to location, the categories are seed, soil"
+        );
+        assert_eq!(part2(input).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_parse_seed_ranges() {
+        let seeds = "seeds: 79 14 55 13";
+        let expected: Vec<(u64, u64)> = vec![(79, 14), (55, 13)];
+        match parse_seeds_ranges(seeds) {
+            Ok((_, result)) => assert_eq!(result, expected),
+            Err(_) => panic!("parse_seeds failed"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = EXAMPLE1.replacen("52 50 48", "52 5x 48", 1);
+        let error = parse(&input).unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.column, 5);
+        assert_eq!(error.snippet, "52 5x 48");
+        assert_eq!(error.expected, "a number");
+
+        let input = EXAMPLE1.replacen("soil-to-fertilizer", "soil-fertilizer", 1);
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 5));
+        assert_eq!(error.expected, "a map header like \"seed-to-soil map:\"");
+    }
+
+    #[test]
+    fn test_part2_with_progress() {
+        let progress = Progress::new();
+        assert_eq!(
+            part2_with(EXAMPLE2, "seed", "location", &progress),
+            Ok(Some(46))
+        );
+        // 79 14 55 13
+        assert_eq!(progress.position(), (27, 27));
+        assert!(!progress.is_cancelled());
+    }
+
+    #[test]
+    fn test_part2_with_cancelled() {
+        // nothing gets looked up, however many seeds there are
+        let input = EXAMPLE2.replace("79 14 55 13", "0 100000000000");
+        let progress = Progress::new();
+        progress.cancel();
+        assert_eq!(part2_with(&input, "seed", "location", &progress), Ok(None));
+        assert_eq!(progress.position(), (0, 100000000000));
+    }
+
+    // seed ranges, then the maps of a chain from seed to location, each map
+    // a list of (dst, src, len)
+    #[derive(Debug, Clone)]
+    struct Almanac {
+        seeds: Vec<SeedRange>,
+        maps: Vec<Vec<(u64, u64, u64)>>,
+    }
+
+    const CATEGORIES: [&str; 6] = [
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+    ];
+
+    impl Almanac {
+        fn names(&self) -> Vec<&str> {
+            let mut names = vec!["seed"];
+            names.extend(&CATEGORIES[..self.maps.len() - 1]);
+            names.push("location");
+            return names;
+        }
+
+        // the map sections in the given order
+        fn render(&self, order: &[usize]) -> String {
+            let names = self.names();
+            let seeds = self
+                .seeds
+                .iter()
+                .map(|(s, l)| format!("{} {}", s, l))
+                .collect::<Vec<_>>();
+            let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+            for &i in order {
+                let mut section = format!("{}-to-{} map:", names[i], names[i + 1]);
+                for (dst, src, len) in &self.maps[i] {
+                    section.push_str(&format!("\n{} {} {}", dst, src, len));
+                }
+                sections.push(section);
+            }
+            return sections.join("\n\n") + "\n";
+        }
+
+        fn location(&self, seed: u64) -> u64 {
+            return self.maps.iter().fold(seed, |value, map| {
+                return map
+                    .iter()
+                    .find(|(_, src, len)| *src <= value && value < src + len)
+                    .map(|(dst, src, _)| dst + value - src)
+                    .unwrap_or(value);
+            });
+        }
+    }
+
+    fn almanac() -> impl Strategy<Value = (Almanac, Vec<usize>)> {
+        let range = (0..1000u64, 0..1000u64, 1..50u64);
+        let map = prop::collection::vec(range, 1..5);
+        return (
+            prop::collection::vec((0..1000u64, 1..20u64), 1..4),
+            prop::collection::vec(map, 1..=CATEGORIES.len() + 1),
+        )
+            .prop_flat_map(|(seeds, maps)| {
+                let order = (0..maps.len()).collect::<Vec<_>>();
+                return (Just(Almanac { seeds, maps }), Just(order).prop_shuffle());
+            });
+    }
+
+    proptest! {
+        #[test]
+        fn prop_part1_matches_reference((almanac, _) in almanac()) {
+            let order = (0..almanac.maps.len()).collect::<Vec<_>>();
+            let expected = almanac
+                .seeds
+                .iter()
+                .flat_map(|&(s, l)| [s, l])
+                .map(|seed| almanac.location(seed))
+                .min()
+                .unwrap();
+            prop_assert_eq!(part1(&almanac.render(&order)), Ok(expected));
+        }
+
+        #[test]
+        fn prop_map_order_does_not_matter((almanac, order) in almanac()) {
+            let sorted = (0..almanac.maps.len()).collect::<Vec<_>>();
+            let input = almanac.render(&sorted);
+            let shuffled = almanac.render(&order);
+            prop_assert_eq!(part1(&shuffled), part1(&input));
+            prop_assert_eq!(part2(&shuffled), part2(&input));
+        }
+
+        #[test]
+        fn prop_part2_matches_reference((almanac, order) in almanac()) {
+            let expected = almanac
+                .seeds
+                .iter()
+                .flat_map(|&(s, l)| s..s + l)
+                .map(|seed| almanac.location(seed))
+                .min()
+                .unwrap();
+            prop_assert_eq!(part2(&almanac.render(&order)), Ok(expected));
+        }
+    }
+}
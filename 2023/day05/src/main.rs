@@ -0,0 +1,10 @@
+use std::error::Error;
+
+use y2023_day05::{part1, part2};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = inputs::open(include_bytes!("input.enc"))?;
+    println!("Part 1: {}", part1(&input)?);
+    println!("Part 2: {}", part2(&input)?);
+    return Ok(());
+}
@@ -0,0 +1,499 @@
+// draws the maps of an almanac as the intervals they move, each one on its
+// own and all of them composed from seed to location, as ASCII or SVG
+use super::{parse_ranges, Map};
+use parsers::ParseError;
+
+// a half-open interval of source values and where its first value maps to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment {
+    pub start: u64,
+    pub end: u64,
+    pub dst: u64,
+}
+
+impl Segment {
+    pub fn dst_end(&self) -> u64 {
+        return self.dst.saturating_add(self.end - self.start);
+    }
+
+    pub fn is_identity(&self) -> bool {
+        return self.start == self.dst;
+    }
+}
+
+// the maps from seed onwards, in the order `find_dest` follows them; an
+// almanac without a seed map is taken in the order of the file
+fn chain<'a, 'b>(maps: &'b [Map<'a>]) -> Vec<&'b Map<'a>> {
+    let mut chain = Vec::new();
+    let mut current = maps.iter().find(|m| m.from == "seed").or(maps.first());
+    while let Some(map) = current {
+        if chain.len() == maps.len() {
+            break;
+        }
+        chain.push(map);
+        current = maps.iter().find(|m| m.from == map.to);
+    }
+
+    return chain;
+}
+
+// every value from here on is left alone by all the maps and seeds
+fn limit(maps: &[&Map], seeds: &[(u64, u64)]) -> u64 {
+    let ranges = maps
+        .iter()
+        .flat_map(|m| m.map.iter())
+        .map(|r| r.src.max(r.dst).saturating_add(r.len));
+    let seeds = seeds.iter().map(|(start, len)| start.saturating_add(*len));
+
+    return ranges.chain(seeds).max().unwrap_or(1).max(1);
+}
+
+// the map as segments covering [0, limit), the gaps between its ranges
+// included as identity segments
+pub fn segments(map: &Map, limit: u64) -> Vec<Segment> {
+    let mut cuts = vec![0, limit];
+    for r in &map.map {
+        cuts.push(r.src.min(limit));
+        cuts.push(r.src.saturating_add(r.len).min(limit));
+    }
+    cuts.sort();
+    cuts.dedup();
+
+    let segments = cuts
+        .windows(2)
+        .map(|w| {
+            // the first range wins where they overlap, like `get_mapping`
+            let found = map
+                .map
+                .iter()
+                .find(|r| r.src <= w[0] && w[0] < r.src.saturating_add(r.len));
+            return Segment {
+                start: w[0],
+                end: w[1],
+                dst: found
+                    .map(|r| r.dst.saturating_add(w[0] - r.src))
+                    .unwrap_or(w[0]),
+            };
+        })
+        .collect::<Vec<_>>();
+
+    return merge(segments);
+}
+
+// joins neighbours that continue each other, so the cuts left over from
+// composing do not show
+fn merge(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut merged: Vec<Segment> = Vec::new();
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if last.end == segment.start && last.dst_end() == segment.dst => {
+                last.end = segment.end;
+            }
+            _ => merged.push(segment),
+        }
+    }
+
+    return merged;
+}
+
+// the pieces [start, end) is cut into by the segments, each with its own dst
+fn split(segments: &[Segment], start: u64, end: u64) -> Vec<Segment> {
+    return segments
+        .iter()
+        .filter(|s| s.start < end && start < s.end)
+        .map(|s| {
+            let from = s.start.max(start);
+            return Segment {
+                start: from,
+                end: s.end.min(end),
+                dst: s.dst.saturating_add(from - s.start),
+            };
+        })
+        .collect();
+}
+
+// the chain of maps as a single one, from the first source to the last destination
+pub fn compose(maps: &[&Map], limit: u64) -> Vec<Segment> {
+    let Some((first, rest)) = maps.split_first() else {
+        return vec![Segment {
+            start: 0,
+            end: limit,
+            dst: 0,
+        }];
+    };
+
+    let mut composed = segments(first, limit);
+    for map in rest {
+        let next = segments(map, limit);
+        composed = composed
+            .iter()
+            .flat_map(|s| {
+                return split(&next, s.dst, s.dst_end())
+                    .into_iter()
+                    .map(move |piece| Segment {
+                        start: s.start + (piece.start - s.dst),
+                        end: s.start + (piece.end - s.dst),
+                        dst: piece.dst,
+                    });
+            })
+            .collect();
+    }
+
+    return merge(composed);
+}
+
+// the intervals a seed range is made of after each map, starting with itself;
+// a range past `limit` is cut short there
+pub fn trace(maps: &[&Map], limit: u64, (start, len): (u64, u64)) -> Vec<Vec<(u64, u64)>> {
+    let end = start.saturating_add(len).min(limit);
+    let mut stages = vec![vec![(start.min(end), end)]];
+    for map in maps {
+        let segments = segments(map, limit);
+        let next = stages
+            .last()
+            .unwrap()
+            .iter()
+            .flat_map(|&(start, end)| split(&segments, start, end))
+            .map(|piece| (piece.dst, piece.dst_end()))
+            .collect();
+        stages.push(next);
+    }
+
+    return stages;
+}
+
+// what gets drawn: every map, their composition and the path of one seed range
+struct Diagram<'a> {
+    limit: u64,
+    panels: Vec<(String, &'a str, &'a str, Vec<Segment>)>,
+    names: Vec<&'a str>,
+    seeds: Option<(u64, u64)>,
+    stages: Vec<Vec<(u64, u64)>>,
+}
+
+fn diagram<'a>(
+    maps: &'a [Map<'a>],
+    ranges: &[(u64, u64)],
+    seeds: Option<(u64, u64)>,
+) -> Diagram<'a> {
+    let chain = chain(maps);
+    let seeds = seeds.or(ranges.first().copied());
+    let limit = limit(
+        &chain,
+        &[ranges, &seeds.into_iter().collect::<Vec<_>>()].concat(),
+    );
+
+    let mut panels = chain
+        .iter()
+        .map(|m| {
+            (
+                format!("{}-to-{}", m.from, m.to),
+                m.from,
+                m.to,
+                segments(m, limit),
+            )
+        })
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (chain.first(), chain.last()) {
+        panels.push((
+            format!("{}-to-{} (composed)", first.from, last.to),
+            first.from,
+            last.to,
+            compose(&chain, limit),
+        ));
+    }
+
+    let mut names = chain.iter().map(|m| m.from).collect::<Vec<_>>();
+    names.extend(chain.last().map(|m| m.to));
+
+    return Diagram {
+        limit,
+        panels,
+        names,
+        seeds,
+        stages: seeds.map(|s| trace(&chain, limit, s)).unwrap_or_default(),
+    };
+}
+
+const WIDTH: usize = 64;
+
+fn column(value: u64, limit: u64) -> usize {
+    return (value as u128 * WIDTH as u128 / limit as u128) as usize;
+}
+
+// the cells of a bar from `start` to `end`, at least one wide
+fn cells(start: u64, end: u64, limit: u64) -> std::ops::Range<usize> {
+    let from = column(start, limit).min(WIDTH - 1);
+    return from..column(end, limit).clamp(from + 1, WIDTH);
+}
+
+fn letter(index: usize) -> char {
+    return (b'a' + (index % 26) as u8) as char;
+}
+
+pub fn ascii(input: &str, seeds: Option<(u64, u64)>) -> Result<String, ParseError> {
+    let (ranges, maps) = parse_ranges(input)?;
+    let diagram = diagram(&maps, &ranges, seeds);
+    let label = diagram.names.iter().map(|n| n.len()).max().unwrap_or(0);
+
+    let mut out = format!(
+        "scale 0..{}, larger values are unchanged by every map\n",
+        diagram.limit
+    );
+    for (title, from, to, segments) in &diagram.panels {
+        // identity segments are dashes, the ones that move get a letter
+        let mut src = vec![' '; WIDTH];
+        let mut dst = vec![' '; WIDTH];
+        let mut legend = String::new();
+        let moved = segments.iter().filter(|s| !s.is_identity()).enumerate();
+        for s in segments.iter().filter(|s| s.is_identity()) {
+            src[cells(s.start, s.end, diagram.limit)].fill('-');
+            dst[cells(s.start, s.end, diagram.limit)].fill('-');
+        }
+        for (i, s) in moved {
+            src[cells(s.start, s.end, diagram.limit)].fill(letter(i));
+            dst[cells(s.dst, s.dst_end(), diagram.limit)].fill(letter(i));
+            legend.push_str(&format!(
+                "  {}  [{}, {}) -> [{}, {})\n",
+                letter(i),
+                s.start,
+                s.end,
+                s.dst,
+                s.dst_end()
+            ));
+        }
+        for s in segments.iter().filter(|s| s.is_identity()) {
+            legend.push_str(&format!("  -  [{}, {}) unchanged\n", s.start, s.end));
+        }
+
+        out.push_str(&format!("\n{}\n", title));
+        out.push_str(&format!(
+            "  {:>label$} |{}|\n",
+            from,
+            src.iter().collect::<String>()
+        ));
+        out.push_str(&format!(
+            "  {:>label$} |{}|\n",
+            to,
+            dst.iter().collect::<String>()
+        ));
+        out.push_str(&legend);
+    }
+
+    if let Some((start, len)) = diagram.seeds {
+        out.push_str(&format!(
+            "\nseeds [{}, {}) through the maps\n",
+            start,
+            start.saturating_add(len)
+        ));
+        for (name, stage) in diagram.names.iter().zip(&diagram.stages) {
+            let mut bar = vec![' '; WIDTH];
+            for &(start, end) in stage {
+                bar[cells(start, end, diagram.limit)].fill('#');
+            }
+            let intervals = stage
+                .iter()
+                .map(|(start, end)| format!("[{}, {})", start, end))
+                .collect::<Vec<_>>()
+                .join(" ");
+            out.push_str(&format!(
+                "  {:>label$} |{}| {}\n",
+                name,
+                bar.iter().collect::<String>(),
+                intervals
+            ));
+        }
+    }
+
+    return Ok(out);
+}
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_LEFT: f64 = 100.0;
+const PANEL_HEIGHT: f64 = 130.0;
+const ROW_HEIGHT: f64 = 24.0;
+
+fn colour(index: usize) -> String {
+    return format!("hsl({}, 60%, 55%)", (index * 47) % 360);
+}
+
+pub fn svg(input: &str, seeds: Option<(u64, u64)>) -> Result<String, ParseError> {
+    let (ranges, maps) = parse_ranges(input)?;
+    let diagram = diagram(&maps, &ranges, seeds);
+    let x = |value: u64| {
+        return SVG_LEFT + value as f64 * (SVG_WIDTH - SVG_LEFT - 20.0) / diagram.limit as f64;
+    };
+    let right = x(diagram.limit);
+
+    let mut body = String::new();
+    let mut y = 10.0;
+    for (title, from, to, segments) in &diagram.panels {
+        let (top, bottom) = (y + 30.0, y + 110.0);
+        body.push_str(&format!(
+            "<text x=\"10\" y=\"{}\" font-weight=\"bold\">{}</text>\n",
+            y + 14.0,
+            title
+        ));
+        for (label, line) in [(from, top), (to, bottom)] {
+            body.push_str(&format!(
+                "<text x=\"10\" y=\"{}\">{}</text>\n<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#333\"/>\n",
+                line + 4.0,
+                label,
+                SVG_LEFT,
+                line,
+                right,
+                line
+            ));
+        }
+        for (i, s) in segments.iter().enumerate() {
+            let fill = if s.is_identity() {
+                "#ccc".to_string()
+            } else {
+                colour(i)
+            };
+            body.push_str(&format!(
+                "<polygon points=\"{:.1},{} {:.1},{} {:.1},{} {:.1},{}\" fill=\"{}\" fill-opacity=\"0.7\" stroke=\"#fff\"><title>[{}, {}) -&gt; [{}, {})</title></polygon>\n",
+                x(s.start), top, x(s.end), top, x(s.dst_end()), bottom, x(s.dst), bottom,
+                fill, s.start, s.end, s.dst, s.dst_end()
+            ));
+        }
+        y += PANEL_HEIGHT;
+    }
+
+    if let Some((start, len)) = diagram.seeds {
+        body.push_str(&format!(
+            "<text x=\"10\" y=\"{}\" font-weight=\"bold\">seeds [{}, {}) through the maps</text>\n",
+            y + 14.0,
+            start,
+            start.saturating_add(len)
+        ));
+        y += 24.0;
+        for (name, stage) in diagram.names.iter().zip(&diagram.stages) {
+            body.push_str(&format!(
+                "<text x=\"10\" y=\"{}\">{}</text>\n<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#ddd\"/>\n",
+                y + 12.0,
+                name,
+                SVG_LEFT,
+                y + 8.0,
+                right,
+                y + 8.0
+            ));
+            for &(start, end) in stage {
+                body.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{}\" width=\"{:.1}\" height=\"16\" fill=\"#c44\"><title>[{}, {})</title></rect>\n",
+                    x(start),
+                    y,
+                    (x(end) - x(start)).max(1.0),
+                    start,
+                    end
+                ));
+            }
+            y += ROW_HEIGHT;
+        }
+    }
+
+    return Ok(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"12\">\n<rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>\n{}</svg>\n",
+        SVG_WIDTH,
+        y + 10.0,
+        body
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_dest, parse};
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    #[test]
+    fn test_segments() {
+        let (_, maps) = parse(EXAMPLE1).unwrap();
+        assert_eq!(
+            segments(&maps[0], 100),
+            [
+                Segment {
+                    start: 0,
+                    end: 50,
+                    dst: 0
+                },
+                Segment {
+                    start: 50,
+                    end: 98,
+                    dst: 52
+                },
+                Segment {
+                    start: 98,
+                    end: 100,
+                    dst: 50
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_matches_find_dest() {
+        let (_, maps) = parse(EXAMPLE1).unwrap();
+        let chain = chain(&maps);
+        assert_eq!(chain.len(), 7);
+
+        let limit = limit(&chain, &[]);
+        let composed = compose(&chain, limit);
+        for value in 0..limit {
+            let segment = composed
+                .iter()
+                .find(|s| s.start <= value && value < s.end)
+                .unwrap();
+            assert_eq!(
+                Some(segment.dst + value - segment.start),
+                find_dest("seed", value, "location", &maps)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let (_, maps) = parse(EXAMPLE1).unwrap();
+        let chain = chain(&maps);
+        let stages = trace(&chain, limit(&chain, &[]), (82, 1));
+        let values = stages.iter().map(|s| s[0].0).collect::<Vec<_>>();
+        assert_eq!(values, [82, 84, 84, 84, 77, 45, 46, 46]);
+
+        // the first range of part 2 holds the lowest location, seed 82
+        let stages = trace(&chain, limit(&chain, &[]), (79, 14));
+        assert!(stages[7].len() > 1);
+        assert_eq!(stages[7].iter().map(|(start, _)| *start).min(), Some(46));
+    }
+
+    #[test]
+    fn test_trace_at_the_end() {
+        let (_, maps) = parse(EXAMPLE1).unwrap();
+        let chain = chain(&maps);
+        let stages = trace(&chain, u64::MAX, (u64::MAX - 5, 10));
+        assert_eq!(stages[0], [(u64::MAX - 5, u64::MAX)]);
+        assert_eq!(stages[7], [(u64::MAX - 5, u64::MAX)]);
+        assert!(ascii(EXAMPLE1, Some((u64::MAX - 5, 10))).is_ok());
+        assert!(svg(EXAMPLE1, Some((u64::MAX - 5, 10))).is_ok());
+    }
+
+    #[test]
+    fn test_ascii() {
+        let out = ascii(EXAMPLE1, None).unwrap();
+        assert!(out.contains("\nseed-to-soil\n"));
+        assert!(out.contains("  b  [98, 100) -> [50, 52)\n"));
+        assert!(out.contains("  -  [0, 50) unchanged\n"));
+        assert!(out.contains("seed-to-location (composed)"));
+        assert!(out.contains("seeds [79, 93) through the maps"));
+        assert!(out.lines().all(|line| line.chars().count() < 200));
+    }
+
+    #[test]
+    fn test_svg() {
+        let out = svg(EXAMPLE1, Some((55, 13))).unwrap();
+        assert!(out.starts_with("<svg"));
+        assert!(out.ends_with("</svg>\n"));
+        assert_eq!(out.matches("<text").count(), 8 * 3 + 1 + 8);
+        assert!(svg("seeds: 1\n", None).is_err());
+    }
+}
@@ -0,0 +1,446 @@
+// a prompt to poke at an almanac while debugging: every command follows
+// values from map to map and shows what they are after each hop
+use std::io::{self, BufRead, Write};
+
+use super::{find_dest, get_mapping, parse, render, Map};
+use parsers::ParseError;
+
+pub const HELP: &str = "\
+commands:
+  map <from> <value> <to>      follow a value, like map seed 79 location
+  path <from> <to>             follow every seed of the almanac, like path seed humidity
+  inverse <category> <value>   the seeds that end up at a value, like inverse location 46
+  show <map>                   the ranges of a map, like show soil-to-fertilizer
+  ranges <from> <start> <len>  follow a range to the last category, like ranges seed 79 14
+  help                         this list
+  quit                         leave, like end of input";
+
+pub struct Almanac<'a> {
+    seeds: Vec<u64>,
+    maps: Vec<Map<'a>>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn parse(input: &'a str) -> Result<Almanac<'a>, ParseError> {
+        let (seeds, maps) = parse(input)?;
+
+        return Ok(Almanac { seeds, maps });
+    }
+
+    // the output of one command line, or what is wrong with it
+    pub fn execute(&self, line: &str) -> Result<String, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        return match words[..] {
+            ["map", from, value, to] => self.map(from, number(value)?, to),
+            ["path", from, to] => self.path(from, to),
+            ["inverse", category, value] => self.inverse(category, number(value)?),
+            ["show", name] => self.show(name),
+            ["ranges", from, start, len] => self.ranges(from, number(start)?, number(len)?),
+            ["help"] => Ok(HELP.to_string()),
+            [] => Ok(String::new()),
+            _ => Err(format!("cannot understand {:?}, try help", line.trim())),
+        };
+    }
+
+    // the maps from `from` on, up to the one that leads to `to`, or to the
+    // last category if there is none
+    fn route(&self, from: &str, to: Option<&str>) -> Result<Vec<&Map<'a>>, String> {
+        let mut route = Vec::new();
+        let mut current = from;
+        while to != Some(current) {
+            let next = self.maps.iter().find(|m| m.from == current);
+            match next {
+                Some(map) if route.len() < self.maps.len() => {
+                    route.push(map);
+                    current = map.to;
+                }
+                _ if to.is_none() && !route.is_empty() => break,
+                _ => {
+                    return Err(match to {
+                        Some(to) => format!("no maps lead from {} to {}", from, to),
+                        None => format!("no map starts at {}", from),
+                    })
+                }
+            }
+        }
+
+        return Ok(route);
+    }
+
+    fn map(&self, from: &str, value: u64, to: &str) -> Result<String, String> {
+        let mut out = format!("{} {}", from, value);
+        let mut current = value;
+        for map in self.route(from, Some(to))? {
+            let next = get_mapping(map, current);
+            let how = match map
+                .map
+                .iter()
+                .find(|r| r.src <= current && current < r.src + r.len)
+            {
+                Some(r) => format!("by {} {} {}", r.dst, r.src, r.len),
+                None => "unmapped".to_string(),
+            };
+            out.push_str(&format!("\n{} {} ({})", map.to, next, how));
+            current = next;
+        }
+
+        return Ok(out);
+    }
+
+    fn path(&self, from: &str, to: &str) -> Result<String, String> {
+        let route = self.route(from, Some(to))?;
+
+        let mut rows = vec![std::iter::once(from)
+            .chain(route.iter().map(|m| m.to))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()];
+        for &seed in &self.seeds {
+            let mut row = vec![seed.to_string()];
+            let mut current = seed;
+            for map in &route {
+                current = get_mapping(map, current);
+                row.push(current.to_string());
+            }
+            rows.push(row);
+        }
+
+        return Ok(table(&rows));
+    }
+
+    // walks the maps backwards; a value can come from a range and from being
+    // left alone at the same time, so every hop is a set of values
+    fn inverse(&self, category: &str, value: u64) -> Result<String, String> {
+        let start = self.start();
+        let route = self.route(start, Some(category))?;
+
+        let mut out = format!("{} {}", category, value);
+        let mut values = vec![value];
+        for map in route.iter().rev() {
+            let mut previous = values
+                .iter()
+                .flat_map(|&v| preimages(map, v))
+                .collect::<Vec<_>>();
+            previous.sort();
+            previous.dedup();
+            out.push_str(&format!("\n{} {}", map.from, list(&previous)));
+            values = previous;
+        }
+
+        // and forwards again, to check the way back; without maps to go
+        // through, the value is a value of the start category already
+        for &seed in &values {
+            let there = match route.is_empty() {
+                true => Some(seed),
+                false => find_dest(start, seed, category, &self.maps),
+            };
+            if there != Some(value) {
+                return Err(format!(
+                    "{} {} maps to {:?}, not {}",
+                    start, seed, there, value
+                ));
+            }
+        }
+        if start == "seed" {
+            let known = values
+                .iter()
+                .filter(|v| self.seeds.contains(v))
+                .copied()
+                .collect::<Vec<_>>();
+            out.push_str(&format!("\nof the almanac's seeds: {}", list(&known)));
+        }
+
+        return Ok(out);
+    }
+
+    fn show(&self, name: &str) -> Result<String, String> {
+        let map = self
+            .maps
+            .iter()
+            .find(|m| format!("{}-to-{}", m.from, m.to) == name)
+            .ok_or(format!("no map {}", name))?;
+
+        let mut ranges = map.map.iter().collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.src);
+        let mut rows = vec![vec![
+            map.from.to_string(),
+            map.to.to_string(),
+            "len".to_string(),
+        ]];
+        for r in ranges {
+            rows.push(vec![
+                format!("{}..{}", r.src, r.src + r.len),
+                format!("{}..{}", r.dst, r.dst + r.len),
+                r.len.to_string(),
+            ]);
+        }
+
+        return Ok(table(&rows));
+    }
+
+    fn ranges(&self, from: &str, start: u64, len: u64) -> Result<String, String> {
+        if len == 0 {
+            return Err("an empty range goes nowhere".to_string());
+        }
+        let route = self.route(from, None)?;
+        let stages = render::trace(&route, u64::MAX, (start, len));
+
+        let names = std::iter::once(from).chain(route.iter().map(|m| m.to));
+        let mut out = Vec::new();
+        for (name, intervals) in names.zip(&stages) {
+            let mut intervals = intervals.clone();
+            intervals.sort();
+            let text = intervals
+                .iter()
+                .map(|(start, end)| format!("{}..{}", start, end))
+                .collect::<Vec<_>>()
+                .join(" ");
+            out.push(format!("{} {}", name, text));
+        }
+        let lowest = stages
+            .last()
+            .and_then(|last| last.iter().map(|&(start, _)| start).min())
+            .ok_or(format!("{} {} {} leads nowhere", from, start, len))?;
+        out.push(format!("lowest {}", lowest));
+
+        return Ok(out.join("\n"));
+    }
+
+    // where the chain of maps begins, the only category no map leads to
+    fn start(&self) -> &'a str {
+        return self
+            .maps
+            .iter()
+            .map(|m| m.from)
+            .find(|&from| !self.maps.iter().any(|m| m.to == from))
+            .unwrap_or("seed");
+    }
+}
+
+// the values `map` sends to `value`
+fn preimages(map: &Map, value: u64) -> Vec<u64> {
+    let mut values = map
+        .map
+        .iter()
+        .filter(|r| r.dst <= value && value < r.dst + r.len)
+        .map(|r| r.src + (value - r.dst))
+        .collect::<Vec<_>>();
+    // values no range takes are left as they are
+    if !map
+        .map
+        .iter()
+        .any(|r| r.src <= value && value < r.src + r.len)
+    {
+        values.push(value);
+    }
+
+    return values;
+}
+
+fn number(word: &str) -> Result<u64, String> {
+    return word
+        .parse()
+        .map_err(|_| format!("{:?} is not a number", word));
+}
+
+fn list(values: &[u64]) -> String {
+    if values.is_empty() {
+        return "none".to_string();
+    }
+
+    return values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+}
+
+// the rows with their columns aligned
+fn table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    return rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+// reads commands until quit or the end of the input, errors go along with
+// the output so a session reads in order
+pub fn run(almanac: &Almanac, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim() == "quit" {
+            break;
+        }
+        match almanac.execute(&line) {
+            Ok(text) if text.is_empty() => {}
+            Ok(text) => writeln!(output, "{}", text)?,
+            Err(error) => writeln!(output, "error: {}", error)?,
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Range;
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    fn execute(line: &str) -> Result<String, String> {
+        return Almanac::parse(EXAMPLE1).unwrap().execute(line);
+    }
+
+    #[test]
+    fn test_map() {
+        // the hops of seed 79 from the puzzle description
+        assert_eq!(
+            execute("map seed 79 location").unwrap(),
+            "seed 79
+soil 81 (by 52 50 48)
+fertilizer 81 (unmapped)
+water 81 (unmapped)
+light 74 (by 18 25 70)
+temperature 78 (by 68 64 13)
+humidity 78 (unmapped)
+location 82 (by 60 56 37)"
+        );
+        assert_eq!(
+            execute("map light 74 temperature").unwrap(),
+            "light 74\ntemperature 78 (by 68 64 13)"
+        );
+        assert_eq!(
+            execute("map location 1 seed"),
+            Err("no maps lead from location to seed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        // the values of each seed from the puzzle description
+        assert_eq!(
+            execute("path seed humidity").unwrap(),
+            "\
+seed  soil  fertilizer  water  light  temperature  humidity
+  79    81          81     81     74           78        78
+  14    14          53     49     42           42        43
+  55    57          57     53     46           82        82
+  13    13          52     41     34           34        35"
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let out = execute("inverse location 46").unwrap();
+        assert!(out.starts_with("location 46\nhumidity 46\n"));
+        assert!(out.contains("\nseed 82\n"));
+        assert!(out.ends_with("of the almanac's seeds: none"));
+
+        // 82 is where seed 79 ends up
+        assert!(execute("inverse location 82")
+            .unwrap()
+            .ends_with("of the almanac's seeds: 79"));
+
+        // no maps to go back through
+        assert_eq!(
+            execute("inverse seed 79"),
+            Ok("seed 79\nof the almanac's seeds: 79".to_string())
+        );
+    }
+
+    #[test]
+    fn test_preimages() {
+        let (_, maps) = parse(EXAMPLE1).unwrap();
+        // seed-to-soil: 50 comes from 98 only, 20 is left alone
+        assert_eq!(preimages(&maps[0], 50), [98]);
+        assert_eq!(preimages(&maps[0], 20), [20]);
+        // 99 comes from 97, and is not left alone as it is mapped itself
+        assert_eq!(preimages(&maps[0], 99), [97]);
+
+        // 12 comes from 2, and is left alone too
+        let map = Map {
+            from: "a",
+            to: "b",
+            map: vec![Range {
+                src: 0,
+                dst: 10,
+                len: 5,
+            }],
+        };
+        assert_eq!(preimages(&map, 12), [2, 12]);
+        assert_eq!(preimages(&map, 2), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(
+            execute("show seed-to-soil").unwrap(),
+            "   seed     soil  len\n 50..98  52..100   48\n98..100   50..52    2"
+        );
+        assert_eq!(
+            execute("show seed-to-dirt"),
+            Err("no map seed-to-dirt".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ranges() {
+        let out = execute("ranges seed 79 14").unwrap();
+        assert!(out.starts_with("seed 79..93\nsoil 81..95\n"));
+        // the lowest location of part 2 comes from this range
+        assert!(out.ends_with("lowest 46"));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(execute("map seed x location")
+            .unwrap_err()
+            .contains("not a number"));
+        assert!(execute("fly seed").unwrap_err().contains("try help"));
+        assert!(execute("ranges seed 79 0").is_err());
+        assert!(execute("ranges seed 18446744073709551615 10").is_err());
+        assert!(execute("ranges seed 18446744073709551610 10")
+            .unwrap()
+            .ends_with("lowest 18446744073709551610"));
+        assert_eq!(execute("  "), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_run() {
+        let almanac = Almanac::parse(EXAMPLE1).unwrap();
+        let mut output = Vec::new();
+        run(
+            &almanac,
+            "map light 74 temperature\nbogus\nquit\nhelp\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "> light 74\ntemperature 78 (by 68 64 13)\n> error: cannot understand \"bogus\", try help\n> "
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// the public API of day05, as another crate sees it
+use y2023_day05::{Map, Range};
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+#[test]
+fn test_parse_map() {
+    let (_, map) = y2023_day05::parse_map("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+    assert_eq!(
+        map,
+        Map {
+            from: "seed",
+            to: "soil",
+            map: vec![
+                Range {
+                    src: 98,
+                    dst: 50,
+                    len: 2
+                },
+                Range {
+                    src: 50,
+                    dst: 52,
+                    len: 48
+                },
+            ],
+        }
+    );
+    assert_eq!(y2023_day05::get_mapping(&map, 99), 51);
+    assert_eq!(y2023_day05::get_mapping(&map, 10), 10);
+}
+
+#[test]
+fn test_parse_and_find_dest() {
+    let (seeds, maps) = y2023_day05::parse(EXAMPLE1).unwrap();
+    assert_eq!(seeds, [79, 14, 55, 13]);
+    assert_eq!(maps.len(), 7);
+    assert_eq!(
+        y2023_day05::find_dest("seed", 79, "location", &maps),
+        Some(82)
+    );
+    assert_eq!(y2023_day05::find_dest("seed", 79, "soil", &maps), Some(81));
+
+    let (ranges, _) = y2023_day05::parse_ranges(EXAMPLE1).unwrap();
+    assert_eq!(ranges, [(79, 14), (55, 13)]);
+}
+
+#[test]
+fn test_parts() {
+    assert_eq!(y2023_day05::part1(EXAMPLE1), Ok(35));
+    assert_eq!(y2023_day05::part2(EXAMPLE1), Ok(46));
+}
@@ -0,0 +1,284 @@
+use nom::{
+    character::complete::{digit1, space1},
+    combinator::map_res,
+    error::context,
+    multi::separated_list0,
+    sequence::{pair, separated_pair, terminated},
+};
+use parsers::{labelled, labelled_numbers, line_end, parse_all, ParseError, Parsed};
+use tracing::instrument;
+
+// a distance past what a u128 holds beats any record, it is kept at the most
+pub fn get_distance(button_hold: u128, race_time: u128) -> u128 {
+    return (race_time - button_hold).saturating_mul(button_hold);
+}
+
+pub fn get_winning_margin(race_time: u128, record: u128) -> Vec<u128> {
+    return (0..race_time)
+        .map(|t| get_distance(t, race_time))
+        .filter(|&d| d > record)
+        .collect::<Vec<_>>();
+}
+
+// None when the product does not fit in a u128
+fn multiply_all(v: Vec<u128>) -> Option<u128> {
+    return v
+        .iter()
+        .try_fold(1u128, |product, &n| product.checked_mul(n));
+}
+
+pub fn get_races(input: &str) -> Result<Vec<(u128, u128)>, ParseError> {
+    let (times, distances) = parse_all(
+        6,
+        input,
+        separated_pair(
+            labelled_numbers::<u128>("Time:"),
+            line_end,
+            labelled_numbers::<u128>("Distance:"),
+        ),
+    )?;
+    // a race needs both; the error is at the end of the line that is short
+    if times.len() != distances.len() {
+        let mut lines = input.lines();
+        let (time_line, distance_line) = (lines.next().unwrap_or(""), lines.next().unwrap_or(""));
+        let (line, expected) = match times.len() < distances.len() {
+            true => (time_line, "as many times as distances"),
+            false => (distance_line, "as many distances as times"),
+        };
+        return Err(ParseError::new(
+            6,
+            input,
+            &line[line.trim_end().len()..],
+            expected,
+        ));
+    }
+    let records = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(&t, &d)| (t, d))
+        .collect::<Vec<_>>();
+
+    return Ok(records);
+}
+
+fn get_labelled_number<'a>(label: &'static str) -> impl FnMut(&'a str) -> Parsed<'a, u128> {
+    // number is the numbers in the string array concatenated
+    let number = map_res(separated_list0(space1, digit1), |array: Vec<&'a str>| {
+        array
+            .iter()
+            .fold(String::new(), |acc, &x| acc + x)
+            .parse::<u128>()
+    });
+
+    return terminated(
+        labelled(label, context("a number that fits in 128 bits", number)),
+        line_end,
+    );
+}
+
+pub fn get_races_2(input: &str) -> Result<(u128, u128), ParseError> {
+    return parse_all(
+        6,
+        input,
+        pair(
+            get_labelled_number("Time:"),
+            get_labelled_number("Distance:"),
+        ),
+    );
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn parse(input: &str) -> Result<Vec<(u128, u128)>, ParseError> {
+    return get_races(input);
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part1(input: &str) -> Result<u128, ParseError> {
+    let races = get_races(input)?;
+
+    let margins = races
+        .iter()
+        .map(|(t, d)| get_winning_margin(*t, *d).len() as u128)
+        .collect::<Vec<_>>();
+
+    return multiply_all(margins).ok_or_else(|| {
+        ParseError::new(
+            6,
+            input,
+            input.trim_start(),
+            "races whose ways to win multiply to less than 2^128",
+        )
+    });
+}
+
+#[instrument(level = "debug", skip_all)]
+pub fn part2(input: &str) -> Result<u128, ParseError> {
+    let (race_time, record) = get_races_2(input)?;
+    return Ok(get_winning_margin(race_time, record).len() as u128);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // the examples from the puzzle description, see `aoc extract`
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+    fixtures::cases! {
+        part1 => example1, input;
+        part2 => example2, input;
+    }
+
+    #[test]
+    fn test_distance() {
+        let measures = [
+            (0, 0),
+            (1, 6),
+            (2, 10),
+            (3, 12),
+            (4, 12),
+            (5, 10),
+            (6, 6),
+            (7, 0),
+        ];
+
+        let duration = 7;
+        for (button_hold, expected) in measures.iter() {
+            assert_eq!(get_distance(*button_hold, duration), *expected);
+        }
+    }
+
+    #[test]
+    fn test_winning_margin() {
+        let races = [(7, 9), (15, 40), (30, 200)];
+        let ways_to_win = [4, 8, 9];
+        let expected = races.iter().zip(ways_to_win.iter());
+
+        for ((duration, record), expected) in expected {
+            let distances = get_winning_margin(*duration, *record);
+            assert_eq!(distances.len(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_product_too_big() {
+        // 999 ways to win each, 999^13 is past 2^128 and 999^12 is not
+        let races = |n| {
+            return format!(
+                "Time: {}\nDistance: {}\n",
+                "1000 ".repeat(n),
+                "0 ".repeat(n)
+            );
+        };
+        assert_eq!(part1(&races(12)), Ok(999u128.pow(12)));
+        let error = part1(&races(13)).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(
+            error.expected,
+            "races whose ways to win multiply to less than 2^128"
+        );
+    }
+
+    #[test]
+    fn test_get_races() {
+        let expected = [(7, 9), (15, 40), (30, 200)];
+
+        let parsed = get_races(EXAMPLE1).unwrap();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_get_races_error() {
+        let error = get_races("Time:      7  15   30\nDistance:  9  4x  200\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 16));
+        assert_eq!(error.snippet, "Distance:  9  4x  200");
+    }
+
+    #[test]
+    fn test_get_races_mismatched() {
+        let error = get_races("Time:      7  15   30\nDistance:  9  40\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 17));
+        assert_eq!(error.expected, "as many distances as times");
+        let error = get_races("Time:      7  15\nDistance:  9  40  200\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 17));
+        assert_eq!(error.expected, "as many times as distances");
+    }
+
+    #[test]
+    fn test_get_races_2_error() {
+        let error = get_races_2("Time: 7\nDistance: 99999999999999999999999999999999999999999")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.expected, "a number that fits in 128 bits");
+    }
+
+    fn races(max_time: u128) -> impl Strategy<Value = Vec<(u128, u128)>> {
+        let race = (1..max_time).prop_flat_map(|time| (Just(time), 0..time * time / 4 + 2));
+        return prop::collection::vec(race, 1..4);
+    }
+
+    // the numbers right-aligned in columns, like the puzzle input
+    fn format_races(races: &[(u128, u128)], width: usize) -> String {
+        let times = races
+            .iter()
+            .map(|(t, _)| format!("{:>width$}", t, width = width));
+        let distances = races
+            .iter()
+            .map(|(_, d)| format!("{:>width$}", d, width = width));
+        return format!(
+            "Time:    {}\nDistance:{}\n",
+            times.collect::<Vec<_>>().join(" "),
+            distances.collect::<Vec<_>>().join(" ")
+        );
+    }
+
+    // the ways to win from where the distance equals the record, using the roots
+    // of hold * (time - hold) = record
+    fn reference(time: u128, record: u128) -> u128 {
+        let (time, record) = (time as f64, record as f64);
+        let discriminant = time * time - 4.0 * record;
+        if discriminant < 0.0 {
+            return 0;
+        }
+        let root = discriminant.sqrt();
+        let low = ((time - root) / 2.0).floor() + 1.0;
+        let high = ((time + root) / 2.0).ceil() - 1.0;
+        return if high < low {
+            0
+        } else {
+            (high - low + 1.0) as u128
+        };
+    }
+
+    proptest! {
+        #[test]
+        fn prop_races_round_trip(races in races(1000), width in 1..8usize) {
+            prop_assert_eq!(get_races(&format_races(&races, width)), Ok(races));
+        }
+
+        #[test]
+        fn prop_part1_matches_reference(races in races(1000)) {
+            let expected = races.iter().map(|&(t, d)| reference(t, d)).product::<u128>();
+            prop_assert_eq!(part1(&format_races(&races, 4)), Ok(expected));
+        }
+
+        #[test]
+        fn prop_winning_distances_are_symmetric(time in 1..500u128, record in 0..70000u128) {
+            // holding for t or for time - t goes just as far
+            let distances = get_winning_margin(time, record);
+            let reversed = distances.iter().rev().cloned().collect::<Vec<_>>();
+            prop_assert_eq!(distances, reversed);
+        }
+
+        #[test]
+        fn prop_part2_joins_the_races(races in races(20)) {
+            let input = format_races(&races, 3);
+            let joined = races.iter().fold((String::new(), String::new()), |(t, d), (time, distance)| {
+                return (t + &time.to_string(), d + &distance.to_string());
+            });
+            let joined = (joined.0.parse::<u128>().unwrap(), joined.1.parse::<u128>().unwrap());
+            prop_assert_eq!(part2(&input), Ok(reference(joined.0, joined.1)));
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// the public API of day06, as another crate sees it
+
+const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+
+#[test]
+fn test_races() {
+    assert_eq!(
+        y2023_day06::get_races(EXAMPLE1),
+        Ok(vec![(7, 9), (15, 40), (30, 200)])
+    );
+    assert_eq!(y2023_day06::get_races_2(EXAMPLE1), Ok((71530, 940200)));
+    assert_eq!(y2023_day06::get_distance(3, 7), 12);
+    assert_eq!(y2023_day06::get_winning_margin(7, 9), [10, 12, 12, 10]);
+}
+
+#[test]
+fn test_parts() {
+    assert_eq!(y2023_day06::part1(EXAMPLE1), Ok(288));
+    assert_eq!(y2023_day06::part2(EXAMPLE1), Ok(71503));
+}
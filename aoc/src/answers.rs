@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// the known correct answers, keyed by (day, part)
+//
+// the file is TOML with one table per day:
+//
+// [day01]
+// part1 = 55477
+// part2 = "54431"
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    // a missing file is the same as an empty one: every answer is unknown
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        return Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in table.iter() {
+            let day = parse_key(day_key, "day")?;
+            let parts = parts
+                .as_table()
+                .ok_or(format!("[{}] should be a table", day_key))?;
+
+            for (part_key, value) in parts.iter() {
+                let part = parse_key(part_key, "part")?;
+                let answer = match value {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Integer(i) => i.to_string(),
+                    _ => {
+                        return Err(format!(
+                            "{}.{} should be a string or an integer",
+                            day_key, part_key
+                        ))
+                    }
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+
+        return Ok(Answers { answers });
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        return self.answers.get(&(day, part)).map(|s| s.as_str());
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Status {
+        return match self.get(day, part) {
+            Some(expected) if expected == answer => Status::Correct,
+            Some(expected) => Status::Wrong {
+                expected: expected.to_string(),
+            },
+            None => Status::Unknown,
+        };
+    }
+}
+
+// turns "day05" into 5 and "part2" into 2
+pub fn parse_key(key: &str, prefix: &str) -> Result<u8, String> {
+    return key
+        .strip_prefix(prefix)
+        .and_then(|n| n.parse::<u8>().ok())
+        .ok_or(format!("bad key {:?}, expected {}<number>", key, prefix));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "
+        [day01]
+        part1 = 142
+        part2 = \"281\"
+
+        [day05]
+        part1 = 35
+    ";
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), Some("281"));
+        assert_eq!(answers.get(5, 1), Some("35"));
+        assert_eq!(answers.get(5, 2), None);
+    }
+
+    #[test]
+    fn test_parse_bad_keys() {
+        assert!(Answers::parse("[dayone]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day01]\nfirst = 1").is_err());
+        assert!(Answers::parse("[day01]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("day01 = 1").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.check(1, 1, "142"), Status::Correct);
+        assert_eq!(
+            answers.check(1, 2, "280"),
+            Status::Wrong {
+                expected: "281".to_string()
+            }
+        );
+        assert_eq!(answers.check(2, 1, "8"), Status::Unknown);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let answers = Answers::load(Path::new("does/not/exist.toml")).unwrap();
+        assert_eq!(answers, Answers::default());
+    }
+}
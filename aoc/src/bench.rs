@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use progress::Progress;
+use serde::{Deserialize, Serialize};
+
+use crate::days::Day;
+use crate::memory::{self, format_bytes, Usage};
+use crate::params::Params;
+use crate::report;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub median_ns: u64,
+    pub min_ns: u64,
+    // in ns², so it stays comparable between runs
+    pub variance_ns2: f64,
+    // what one more run allocated, when asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Usage>,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to compute stats from");
+
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        nanos.sort();
+
+        let middle = nanos.len() / 2;
+        let median_ns = if nanos.len() % 2 == 0 {
+            (nanos[middle - 1] + nanos[middle]) / 2
+        } else {
+            nanos[middle]
+        };
+
+        let mean = nanos.iter().sum::<u64>() as f64 / nanos.len() as f64;
+        let variance_ns2 = nanos
+            .iter()
+            .map(|&n| (n as f64 - mean).powi(2))
+            .sum::<f64>()
+            / nanos.len() as f64;
+
+        return Stats {
+            median_ns,
+            min_ns: nanos[0],
+            variance_ns2,
+            memory: None,
+        };
+    }
+}
+
+pub struct Measurement {
+    pub day: String,
+    // "parse", "part1" or "part2"
+    pub stage: String,
+    pub stats: Stats,
+}
+
+pub fn measure<F: Fn()>(runs: usize, f: F) -> Stats {
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+
+    return Stats::from_samples(&samples);
+}
+
+// with `memory`, each stage is run once more with its allocations counted,
+// apart from the timed runs that the counting would slow down
+pub fn bench_day(day: &Day, input: &str, runs: usize, memory: bool) -> Vec<Measurement> {
+    let mut measurements = Vec::new();
+    let mut push = |stage: String, mut stats: Stats, f: &dyn Fn()| {
+        if memory {
+            stats.memory = memory::track(f).1;
+        }
+        measurements.push(Measurement {
+            day: day.name(),
+            stage,
+            stats,
+        })
+    };
+
+    if let Some(parse) = day.parse {
+        let f = || parse(black_box(input));
+        push("parse".to_string(), measure(runs, f), &f);
+    }
+    let params = Params::defaults(day.params);
+    for (part, solver) in day.parts() {
+        let f = || {
+            let _ = black_box(solver(black_box(input), &params, &Progress::new()));
+        };
+        push(format!("part{}", part), measure(runs, f), &f);
+    }
+
+    return measurements;
+}
+
+// the stats of an earlier run, keyed by day and then by stage
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline(BTreeMap<String, BTreeMap<String, Stats>>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        return toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    // keeps the entries for days and stages that were not measured this time,
+    // and the memory of the ones whose memory was not
+    pub fn save(&mut self, path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+        for m in measurements {
+            let stages = self.0.entry(m.day.clone()).or_default();
+            let mut stats = m.stats;
+            if stats.memory.is_none() {
+                stats.memory = stages.get(&m.stage).and_then(|base| base.memory);
+            }
+            stages.insert(m.stage.clone(), stats);
+        }
+
+        let text = toml::to_string(self).map_err(|e| e.to_string())?;
+        return fs::write(path, text)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e));
+    }
+
+    pub fn get(&self, day: &str, stage: &str) -> Option<&Stats> {
+        return self.0.get(day).and_then(|stages| stages.get(stage));
+    }
+}
+
+pub fn format_duration(nanos: u64) -> String {
+    return match nanos {
+        n if n < 1_000 => format!("{} ns", n),
+        n if n < 1_000_000 => format!("{:.2} µs", n as f64 / 1e3),
+        n if n < 1_000_000_000 => format!("{:.2} ms", n as f64 / 1e6),
+        n => format!("{:.2} s", n as f64 / 1e9),
+    };
+}
+
+fn change(value: u64, base: Option<u64>) -> String {
+    return match base {
+        Some(base) if base > 0 => format!("{:+.1}%", (value as f64 / base as f64 - 1.0) * 100.0),
+        _ => "-".to_string(),
+    };
+}
+
+// the memory columns are there when any stage had its allocations counted;
+// a note under the table says the parts parse again when a day has a parse
+// stage, so that its time is not taken away from theirs
+pub fn table(measurements: &[Measurement], baseline: &Baseline) -> String {
+    let with_memory = measurements.iter().any(|m| m.stats.memory.is_some());
+    let rows = measurements
+        .iter()
+        .map(|m| {
+            let base = baseline.get(&m.day, &m.stage);
+            let mut row = vec![
+                m.day.clone(),
+                m.stage.clone(),
+                format_duration(m.stats.median_ns),
+                format_duration(m.stats.min_ns),
+                format!("{:.4}", m.stats.variance_ns2 / 1e12),
+                change(m.stats.median_ns, base.map(|base| base.median_ns)),
+            ];
+            if with_memory {
+                row.extend(match m.stats.memory {
+                    Some(usage) => [
+                        usage.allocations.to_string(),
+                        format_bytes(usage.allocated_bytes),
+                        format_bytes(usage.peak_bytes),
+                        change(
+                            usage.peak_bytes,
+                            base.and_then(|base| base.memory)
+                                .map(|base| base.peak_bytes),
+                        ),
+                    ],
+                    None => ["-", "-", "-", "-"].map(String::from),
+                });
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut header = vec![
+        "day",
+        "stage",
+        "median",
+        "min",
+        "variance (ms²)",
+        "vs baseline",
+    ];
+    if with_memory {
+        header.extend(["allocs", "allocated", "peak", "peak vs baseline"]);
+    }
+    let mut table = report::columns(&header, &rows);
+    if measurements.iter().any(|m| m.stage == "parse") {
+        table.push_str("\nthe parts parse the input again, their times include the parse");
+    }
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        return values.iter().map(|&ms| Duration::from_millis(ms)).collect();
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&millis(&[3, 1, 2]));
+        assert_eq!(stats.median_ns, 2_000_000);
+        assert_eq!(stats.min_ns, 1_000_000);
+        // mean is 2ms, so the variance is (1 + 0 + 1) / 3 ms²
+        assert!((stats.variance_ns2 / 1e12 - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 1, 2, 3]));
+        assert_eq!(stats.median_ns, 2_500_000);
+        assert_eq!(stats.min_ns, 1_000_000);
+    }
+
+    #[test]
+    fn test_stats_constant() {
+        let stats = Stats::from_samples(&millis(&[5, 5, 5]));
+        assert_eq!(stats.variance_ns2, 0.0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(12), "12 ns");
+        assert_eq!(format_duration(1_500), "1.50 µs");
+        assert_eq!(format_duration(2_250_000), "2.25 ms");
+        assert_eq!(format_duration(80_000_000_000), "80.00 s");
+    }
+
+    #[test]
+    fn test_table_against_baseline() {
+        let stats = |median_ns| Stats {
+            median_ns,
+            min_ns: median_ns,
+            variance_ns2: 0.0,
+            memory: None,
+        };
+        let measurements = vec![
+            Measurement {
+                day: "day06".to_string(),
+                stage: "part1".to_string(),
+                stats: stats(1_500),
+            },
+            Measurement {
+                day: "day06".to_string(),
+                stage: "part2".to_string(),
+                stats: stats(2_000),
+            },
+        ];
+        let mut baseline = Baseline::default();
+        baseline
+            .0
+            .entry("day06".to_string())
+            .or_default()
+            .insert("part1".to_string(), stats(1_000));
+
+        let table = table(&measurements, &baseline);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("day    stage  median"));
+        assert!(lines[2].ends_with("+50.0%"));
+        assert!(lines[3].ends_with("-"));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.0.entry("day01".to_string()).or_default().insert(
+            "part1".to_string(),
+            Stats {
+                median_ns: 10,
+                min_ns: 8,
+                variance_ns2: 1.5,
+                memory: Some(Usage {
+                    allocations: 3,
+                    allocated_bytes: 4096,
+                    peak_bytes: 2048,
+                }),
+            },
+        );
+        let text = toml::to_string(&baseline).unwrap();
+        assert_eq!(toml::from_str::<Baseline>(&text).unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_table_with_memory() {
+        let stats = |peak_bytes| Stats {
+            median_ns: 1_000,
+            min_ns: 1_000,
+            variance_ns2: 0.0,
+            memory: Some(Usage {
+                allocations: 1000,
+                allocated_bytes: 3 << 20,
+                peak_bytes,
+            }),
+        };
+        let measurements = vec![
+            Measurement {
+                day: "day06".to_string(),
+                stage: "parse".to_string(),
+                stats: stats(1 << 20),
+            },
+            Measurement {
+                day: "day06".to_string(),
+                stage: "part2".to_string(),
+                stats: stats(512),
+            },
+        ];
+        let mut baseline = Baseline::default();
+        baseline
+            .0
+            .entry("day06".to_string())
+            .or_default()
+            .insert("parse".to_string(), stats(1 << 19));
+
+        let table = table(&measurements, &baseline);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[0].ends_with("allocs  allocated  peak      peak vs baseline"));
+        assert!(lines[2].ends_with("1000    3.00 MiB   1.00 MiB  +100.0%"));
+        assert!(lines[3].ends_with("1000    3.00 MiB   512 B     -"));
+        assert_eq!(
+            lines[4],
+            "the parts parse the input again, their times include the parse"
+        );
+    }
+
+    #[test]
+    fn test_save_keeps_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("benchmarks.toml");
+        let usage = Usage {
+            allocations: 2,
+            allocated_bytes: 64,
+            peak_bytes: 32,
+        };
+        let measurement = |median_ns, memory| Measurement {
+            day: "day01".to_string(),
+            stage: "part1".to_string(),
+            stats: Stats {
+                median_ns,
+                min_ns: median_ns,
+                variance_ns2: 0.0,
+                memory,
+            },
+        };
+
+        let mut baseline = Baseline::default();
+        baseline
+            .save(&path, &[measurement(10, Some(usage))])
+            .unwrap();
+        // timed again without counting, the counts of before are still there
+        baseline.save(&path, &[measurement(20, None)]).unwrap();
+        let saved = Baseline::load(&path).unwrap();
+        assert_eq!(
+            saved.get("day01", "part1"),
+            Some(&measurement(20, Some(usage)).stats)
+        );
+    }
+}
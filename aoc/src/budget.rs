@@ -0,0 +1,132 @@
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use parsers::ParseError;
+use progress::Progress;
+use tracing::Span;
+
+// how often the progress bar is redrawn and the budget looked at
+const TICK: Duration = Duration::from_millis(100);
+
+// runs the solver, given the input and parameters of the part, on a thread
+// of its own and draws its progress under `label`, if it reports any and
+// there is a label; None once the budget is spent, after asking the solver
+// to stop
+//
+// a solver that never looks at the cancellation keeps its thread busy until
+// it finishes or the runner exits, but the runner does not wait for it
+pub fn run(
+    label: Option<&str>,
+    solver: impl FnOnce(&Progress) -> Result<String, ParseError> + Send + 'static,
+    budget: Option<Duration>,
+) -> Option<Result<String, ParseError>> {
+    let progress = Arc::new(Progress::new());
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let progress = progress.clone();
+        // the part's span, so what the solver logs stays under it
+        let span = Span::current();
+        thread::spawn(move || {
+            let result = span.in_scope(|| solver(&progress));
+            // nobody listens any more after a timeout
+            let _ = tx.send(result);
+        })
+    };
+
+    let bar = ProgressBar::hidden();
+    let start = Instant::now();
+    let result = loop {
+        match rx.recv_timeout(TICK) {
+            Ok(result) => break Some(result),
+            // the solver panicked, as loudly as if it ran here
+            Err(RecvTimeoutError::Disconnected) => match worker.join() {
+                Err(panic) => panic::resume_unwind(panic),
+                Ok(()) => unreachable!("the solver sends its result before it returns"),
+            },
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        if budget.is_some_and(|budget| start.elapsed() >= budget) {
+            progress.cancel();
+            break None;
+        }
+        if let Some(label) = label {
+            draw(&bar, label, &progress);
+        }
+    };
+    bar.finish_and_clear();
+
+    return result;
+}
+
+// the bar only shows up once the solver said how much there is to do, and
+// when stderr is a terminal
+fn draw(bar: &ProgressBar, label: &str, progress: &Progress) {
+    let (done, total) = progress.position();
+    if total == 0 {
+        return;
+    }
+    if bar.length().is_none() {
+        bar.set_style(
+            ProgressStyle::with_template("{msg} [{bar:40}] {percent}% eta {eta}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+        bar.set_message(label.to_string());
+        bar.set_draw_target(ProgressDrawTarget::stderr());
+    }
+    bar.set_length(total);
+    bar.set_position(done);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let input = "21".to_string();
+        let result = run(None, move |_| Ok(input.repeat(2)), None);
+        assert_eq!(result, Some(Ok("2121".to_string())));
+    }
+
+    #[test]
+    fn test_run_within_budget() {
+        let result = run(
+            None,
+            |_| Ok("21".to_string()),
+            Some(Duration::from_secs(60)),
+        );
+        assert_eq!(result, Some(Ok("21".to_string())));
+    }
+
+    #[test]
+    fn test_run_timed_out() {
+        // counts until told to stop
+        let start = Instant::now();
+        let result = run(
+            None,
+            |progress| {
+                progress.set_total(u64::MAX);
+                while !progress.is_cancelled() {
+                    progress.advance(1);
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Ok("cancelled".to_string())
+            },
+            Some(Duration::from_millis(200)),
+        );
+        assert_eq!(result, None);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn test_run_panicked() {
+        run(None, |_| panic!("boom"), None);
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use parsers::ParseError;
+use progress::Progress;
+
+use crate::params::{Param, Params};
+
+// one module per year, each with the registry of its days
+mod y2023;
+// `aoc new` declares the years it creates above this line
+
+// every solution is wrapped so that the answer comes back as a string,
+// whatever integer type the day happens to use; the solutions that take long
+// enough to need it report to the progress and stop when it is cancelled
+pub type Solver = fn(&str, &Params, &Progress) -> Result<String, ParseError>;
+
+// runs only the parsing step of a day, for the days that have one; what it
+// parses is dropped, the parts take the input as text and parse it again
+pub type Parser = fn(&str);
+
+pub struct Day {
+    pub year: u16,
+    pub number: u8,
+    // the constants of the puzzle the solutions can be run with other values of
+    pub params: &'static [Param],
+    // timed on its own, so the time of a part is its parse and its solve
+    // together, not the solve alone
+    pub parse: Option<Parser>,
+    pub part1: Solver,
+    pub part2: Option<Solver>,
+}
+
+impl Day {
+    pub fn name(&self) -> String {
+        return format!("day{:02}", self.number);
+    }
+
+    // the package of the day's crate, like y2023-day05; the year keeps the
+    // days of different years apart
+    pub fn package(&self) -> String {
+        return format!("y{}-{}", self.year, self.name());
+    }
+
+    pub fn dir(&self, root: &Path) -> PathBuf {
+        return dir(root, self.year, self.number);
+    }
+
+    // encrypted, see `aoc encrypt`
+    pub fn input_path(&self, root: &Path) -> PathBuf {
+        return self.dir(root).join("src").join("input.enc");
+    }
+
+    pub fn solver(&self, part: u8) -> Option<Solver> {
+        return match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        };
+    }
+
+    // the parts this day has a solution for, in order
+    pub fn parts(&self) -> Vec<(u8, Solver)> {
+        return (1..=2)
+            .filter_map(|part| self.solver(part).map(|solver| (part, solver)))
+            .collect();
+    }
+}
+
+// the registry of all days the runner knows about, by year
+pub fn all() -> Vec<Day> {
+    let mut days = [
+        y2023::days(),
+        // `aoc new` registers the years it creates above this line
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    // a year can be added after the ones that follow it
+    days.sort_by_key(|day| (day.year, day.number));
+
+    return days;
+}
+
+// the years with at least one day, in order
+pub fn years() -> Vec<u16> {
+    let mut years = all().iter().map(|day| day.year).collect::<Vec<_>>();
+    years.dedup();
+    return years;
+}
+
+// where the crate of a day lives, whether it exists yet or not
+pub fn dir(root: &Path, year: u16, number: u8) -> PathBuf {
+    return root
+        .join(year.to_string())
+        .join(format!("day{:02}", number));
+}
+
+pub fn find(year: u16, number: u8) -> Option<Day> {
+    return all()
+        .into_iter()
+        .find(|day| day.year == year && day.number == number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let numbers = all()
+            .iter()
+            .map(|day| (day.year, day.number))
+            .collect::<Vec<_>>();
+        let mut sorted = numbers.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(numbers, sorted);
+    }
+
+    #[test]
+    fn test_input_path() {
+        let day = find(2023, 5).unwrap();
+        assert_eq!(
+            day.input_path(Path::new("root")),
+            Path::new("root/2023/day05/src/input.enc")
+        );
+        assert_eq!(day.package(), "y2023-day05");
+        assert!(find(2022, 5).is_none());
+    }
+
+    #[test]
+    fn test_years() {
+        assert_eq!(years(), [2023]);
+    }
+
+    #[test]
+    fn test_parts() {
+        let parts = |n| {
+            find(2023, n)
+                .unwrap()
+                .parts()
+                .iter()
+                .map(|(p, _)| *p)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(parts(1), vec![1, 2]);
+        assert_eq!(parts(3), vec![1]);
+        assert!(find(2023, 6).unwrap().solver(3).is_none());
+    }
+
+    #[test]
+    fn test_sample() {
+        let day = find(2023, 6).unwrap();
+        let input = include_str!("../../2023/day06/fixtures/example1.txt");
+        let (params, progress) = (Params::defaults(day.params), Progress::new());
+        assert_eq!(
+            (day.part1)(input, &params, &progress),
+            Ok("288".to_string())
+        );
+        assert_eq!(
+            (day.part2.unwrap())(input, &params, &progress),
+            Ok("71503".to_string())
+        );
+    }
+}
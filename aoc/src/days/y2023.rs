@@ -0,0 +1,115 @@
+// the days of 2023, the crates in 2023/
+use std::borrow::Cow;
+
+use super::Day;
+use crate::params::{Param, Value};
+
+pub fn days() -> Vec<Day> {
+    return vec![
+        Day {
+            year: 2023,
+            number: 1,
+            params: &[],
+            parse: Some(|input| {
+                let _ = y2023_day01::parse(input);
+            }),
+            part1: |input, _, _| Ok(y2023_day01::part1(input)?.to_string()),
+            part2: Some(|input, _, _| Ok(y2023_day01::part2(input)?.to_string())),
+        },
+        Day {
+            year: 2023,
+            number: 2,
+            params: &[
+                Param {
+                    name: "red",
+                    default: Value::Number(12),
+                    help: "red cubes in the bag",
+                },
+                Param {
+                    name: "green",
+                    default: Value::Number(13),
+                    help: "green cubes in the bag",
+                },
+                Param {
+                    name: "blue",
+                    default: Value::Number(14),
+                    help: "blue cubes in the bag",
+                },
+            ],
+            parse: Some(|input| {
+                let _ = y2023_day02::parse(input);
+            }),
+            part1: |input, params, _| {
+                // more cubes than a u32 holds are as good as no limit
+                let count = |color| params.number(color).try_into().unwrap_or(u32::MAX);
+                let cubes = y2023_day02::Cubes {
+                    red: count("red"),
+                    blue: count("blue"),
+                    green: count("green"),
+                };
+                Ok(y2023_day02::part1(input, &cubes)?.to_string())
+            },
+            part2: None,
+        },
+        Day {
+            year: 2023,
+            number: 3,
+            params: &[],
+            parse: Some(|input| {
+                let _ = y2023_day03::parse(input);
+            }),
+            part1: |input, _, _| Ok(y2023_day03::part1(input)?.to_string()),
+            part2: None,
+        },
+        Day {
+            year: 2023,
+            number: 4,
+            params: &[],
+            parse: Some(|input| {
+                let _ = y2023_day04::parse(input);
+            }),
+            part1: |input, _, _| Ok(y2023_day04::part1(input)?.to_string()),
+            part2: None,
+        },
+        Day {
+            year: 2023,
+            number: 5,
+            params: &[
+                Param {
+                    name: "from",
+                    default: Value::Text(Cow::Borrowed("seed")),
+                    help: "the category the seeds are numbers of",
+                },
+                Param {
+                    name: "to",
+                    default: Value::Text(Cow::Borrowed("location")),
+                    help: "the category to find the lowest number of",
+                },
+            ],
+            parse: Some(|input| {
+                let _ = y2023_day05::parse(input);
+            }),
+            part1: |input, params, _| {
+                let lowest =
+                    y2023_day05::part1_between(input, params.text("from"), params.text("to"))?;
+                Ok(lowest.map_or("none".to_string(), |n| n.to_string()))
+            },
+            part2: Some(|input, params, progress| {
+                let (from, to) = (params.text("from"), params.text("to"));
+                let lowest = y2023_day05::part2_with(input, from, to, progress)?;
+                Ok(lowest.map_or("none".to_string(), |n| n.to_string()))
+            }),
+        },
+        Day {
+            year: 2023,
+            number: 6,
+            params: &[],
+            parse: Some(|input| {
+                let _ = y2023_day06::parse(input);
+            }),
+            part1: |input, _, _| Ok(y2023_day06::part1(input)?.to_string()),
+            part2: Some(|input, _, _| Ok(y2023_day06::part2(input)?.to_string())),
+        },
+        // `aoc new` registers the days it creates above this line
+    ];
+}
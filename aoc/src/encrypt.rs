@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use inputs::{InputError, Key};
+
+use crate::days::Day;
+
+// where a day's input goes before `aoc encrypt` seals it, gitignored
+pub fn plain_path(day: &Day, root: &Path) -> PathBuf {
+    return day.input_path(root).with_file_name("input.txt");
+}
+
+// the text of a file that may or may not be encrypted, with the key of the
+// repository at `root`
+pub fn read(root: &Path, path: &Path) -> Result<String, String> {
+    return read_with(path, || Key::load(root));
+}
+
+fn read_with(path: &Path, key: impl FnOnce() -> Result<Key, InputError>) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    return inputs::open_with(&bytes, key).map_err(|e| format!("{}: {}", path.display(), e));
+}
+
+// the key of the repository, or a new one written to its keyfile when there
+// is none anywhere yet; says so if it made one
+pub fn key(root: &Path) -> Result<(Key, Option<PathBuf>), String> {
+    return match Key::load(root) {
+        Ok(key) => Ok((key, None)),
+        Err(InputError::NoKey { keyfile }) => {
+            let secret = Key::generate();
+            fs::write(&keyfile, format!("{}\n", secret))
+                .map_err(|e| format!("cannot write {}: {}", keyfile.display(), e))?;
+            Ok((Key::new(&secret), Some(keyfile)))
+        }
+        Err(e) => Err(e.to_string()),
+    };
+}
+
+// replaces the plain input with its encrypted copy; returns the copy, or
+// nothing if there was no plain input to encrypt
+pub fn seal(key: &Key, plain: &Path, sealed: &Path) -> Result<Option<PathBuf>, String> {
+    if !plain.exists() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(plain).map_err(|e| format!("cannot read {}: {}", plain.display(), e))?;
+    fs::write(sealed, key.encrypt(&text))
+        .map_err(|e| format!("cannot write {}: {}", sealed.display(), e))?;
+    fs::remove_file(plain).map_err(|e| format!("cannot remove {}: {}", plain.display(), e))?;
+
+    return Ok(Some(sealed.to_path_buf()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days;
+    use crate::params::Params;
+    use progress::Progress;
+
+    #[test]
+    fn test_seal() {
+        let dir = tempfile::tempdir().unwrap();
+        let (plain, sealed) = (dir.path().join("input.txt"), dir.path().join("input.enc"));
+        let key = Key::new("hunter2");
+        assert_eq!(seal(&key, &plain, &sealed), Ok(None));
+
+        fs::write(&plain, "Time: 7 15 30\nDistance: 9 40 200\n").unwrap();
+        assert_eq!(seal(&key, &plain, &sealed), Ok(Some(sealed.clone())));
+        assert!(!plain.exists());
+        let bytes = fs::read(&sealed).unwrap();
+        assert!(inputs::is_encrypted(&bytes));
+        assert_eq!(
+            key.decrypt(&bytes).unwrap(),
+            "Time: 7 15 30\nDistance: 9 40 200\n"
+        );
+    }
+
+    #[test]
+    fn test_no_answer_without_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.enc");
+        let sealed = Key::new("hunter2").encrypt("Time: 7 15 30\nDistance: 9 40 200\n");
+        fs::write(&path, sealed).unwrap();
+
+        // a missing or wrong key is caught before a solution ever sees the input
+        let keyfile = dir.path().join(inputs::KEYFILE);
+        let error = read_with(&path, || Err(InputError::NoKey { keyfile })).unwrap_err();
+        assert!(error.starts_with(&format!("{}: the input is encrypted", path.display())));
+        assert!(error.ends_with(".input-key"));
+        let error = read_with(&path, || Ok(Key::new("hunter3"))).unwrap_err();
+        assert!(error.contains("does not decrypt with this key"));
+
+        // with the right one, the answers are the ones of the plain input
+        let day = days::find(2023, 6).unwrap();
+        let input = read_with(&path, || Ok(Key::new("hunter2"))).unwrap();
+        assert_eq!(
+            (day.part1)(&input, &Params::defaults(day.params), &Progress::new()),
+            Ok("288".to_string())
+        );
+    }
+
+    #[test]
+    fn test_plain_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("example1.txt");
+        fs::write(&path, "1abc2\n").unwrap();
+        // no key is needed for them
+        let no_key = || Err(InputError::WrongKey);
+        assert_eq!(read_with(&path, no_key), Ok("1abc2\n".to_string()));
+        assert!(read_with(&dir.path().join("missing.txt"), no_key).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// the example and its expected answer, for one part of a puzzle
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+// a saved puzzle page has one <article> per part that is unlocked; the first
+// <pre><code> block of a part is its example and the last emphasized code is
+// the answer for it. Part two often reuses the example from part one.
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+
+    for article in sections(page, "<article", "</article>") {
+        let input = sections(article, "<pre><code>", "</code></pre>")
+            .first()
+            .map(|block| decode(&strip_tags(block)))
+            .or(examples.last().map(|e| e.input.clone()));
+        let answer = sections(article, "<code><em>", "</em></code>")
+            .last()
+            .map(|answer| decode(&strip_tags(answer)));
+
+        if let Some(input) = input {
+            examples.push(Example { input, answer });
+        }
+    }
+
+    return examples;
+}
+
+// writes exampleN.txt and exampleN.answer for each part into YYYY/dayNN/fixtures
+pub fn write_fixtures(dir: &Path, examples: &[Example]) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+
+    let mut written = Vec::new();
+    for (i, example) in examples.iter().enumerate() {
+        let mut files = vec![(format!("example{}.txt", i + 1), &example.input)];
+        if let Some(answer) = &example.answer {
+            files.push((format!("example{}.answer", i + 1), answer));
+        }
+
+        for (name, content) in files {
+            let path = dir.join(name);
+            fs::write(&path, format!("{}\n", content.trim_end()))
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+            written.push(path);
+        }
+    }
+
+    return Ok(written);
+}
+
+// everything between each start and the end that follows it
+fn sections<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = text;
+
+    while let Some(i) = rest.find(start) {
+        let after = &rest[i + start.len()..];
+        // an opening tag with attributes, like <article class="day-desc">
+        let after = if start.ends_with('>') {
+            after
+        } else {
+            &after[after.find('>').map(|j| j + 1).unwrap_or(0)..]
+        };
+        match after.find(end) {
+            Some(j) => {
+                found.push(&after[..j]);
+                rest = &after[j + end.len()..];
+            }
+            None => break,
+        }
+    }
+
+    return found;
+}
+
+// examples can highlight parts of themselves with <em>, only the text matters
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    return text;
+}
+
+fn decode(text: &str) -> String {
+    return text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><head><title>Day 1 - Advent of Code 2023</title></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>55477</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>eight</em>wothree
+x &lt; y &amp;&amp; z
+</code></pre>
+<p>Adding these together produces <code><em>281</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(PAGE);
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    input: "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".to_string(),
+                    answer: Some("142".to_string()),
+                },
+                Example {
+                    input: "two1nine\neightwothree\nx < y && z\n".to_string(),
+                    answer: Some("281".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_reuses_first_example() {
+        let page = "<article><pre><code>7 15\n</code></pre><code><em>288</em></code></article>
+            <article><p>Now it is one race.</p><code><em>71503</em></code></article>";
+        let examples = extract(page);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].input, "7 15\n");
+        assert_eq!(examples[1].answer, Some("71503".to_string()));
+    }
+
+    #[test]
+    fn test_extract_without_answer() {
+        let examples = extract("<article><pre><code>1 2 3</code></pre></article>");
+        assert_eq!(
+            examples,
+            vec![Example {
+                input: "1 2 3".to_string(),
+                answer: None
+            }]
+        );
+        assert!(extract("<html>no puzzle here</html>").is_empty());
+    }
+
+    #[test]
+    fn test_write_fixtures() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixtures = dir.path().join("fixtures");
+        let written = write_fixtures(&fixtures, &extract(PAGE)).unwrap();
+
+        assert_eq!(written.len(), 4);
+        assert_eq!(
+            fs::read_to_string(fixtures.join("example1.txt")).unwrap(),
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        );
+        assert_eq!(
+            fs::read_to_string(fixtures.join("example2.answer")).unwrap(),
+            "281\n"
+        );
+    }
+}
@@ -0,0 +1,745 @@
+use std::collections::BTreeMap;
+
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::params::{self, Param, Params, Value};
+
+// a synthetic input, with the answers the reference solutions give for it;
+// a part whose answer is too big for its solution to hold is left out, the
+// solution reports an error for that input instead
+pub struct Generated {
+    pub input: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    pub params: &'static [Param],
+    generate: fn(&mut ChaCha8Rng, &Params) -> Generated,
+}
+
+impl Generator {
+    // `values` are name=value pairs; the same seed and values always give the
+    // same input, whatever machine it runs on
+    pub fn generate(&self, seed: u64, values: &[String]) -> Result<Generated, String> {
+        let params = self.parse_params(values)?;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        return Ok((self.generate)(&mut rng, &params));
+    }
+
+    // unlike the --param of a run, every name has to be one of the
+    // generator's, like the names in a config file
+    fn parse_params(&self, values: &[String]) -> Result<Params, String> {
+        let given = params::parse_overrides(values)?
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        return Params::new(self.params, &given, &[])
+            .map_err(|e| format!("day {}: {}", self.day, e));
+    }
+}
+
+pub fn all() -> Vec<Generator> {
+    return vec![
+        Generator {
+            year: 2023,
+            day: 1,
+            params: &[
+                Param {
+                    name: "lines",
+                    default: Value::Number(1000),
+                    help: "number of calibration lines",
+                },
+                Param {
+                    name: "filler",
+                    default: Value::Number(6),
+                    help: "most letters between two digits",
+                },
+                Param {
+                    name: "words",
+                    default: Value::Number(40),
+                    help: "percentage of digits that are spelled out",
+                },
+            ],
+            generate: day01,
+        },
+        Generator {
+            year: 2023,
+            day: 2,
+            params: &[
+                Param {
+                    name: "games",
+                    default: Value::Number(100),
+                    help: "number of games",
+                },
+                Param {
+                    name: "handfuls",
+                    default: Value::Number(6),
+                    help: "most handfuls in a game",
+                },
+                Param {
+                    name: "cubes",
+                    default: Value::Number(20),
+                    help: "most cubes of one color in a handful",
+                },
+            ],
+            generate: day02,
+        },
+        Generator {
+            year: 2023,
+            day: 3,
+            params: &[
+                Param {
+                    name: "width",
+                    default: Value::Number(140),
+                    help: "characters per line",
+                },
+                Param {
+                    name: "height",
+                    default: Value::Number(140),
+                    help: "number of lines",
+                },
+                Param {
+                    name: "numbers",
+                    default: Value::Number(15),
+                    help: "percentage of cells where a number starts",
+                },
+                Param {
+                    name: "symbols",
+                    default: Value::Number(5),
+                    help: "percentage of cells holding a symbol",
+                },
+            ],
+            generate: day03,
+        },
+        Generator {
+            year: 2023,
+            day: 4,
+            params: &[
+                Param {
+                    name: "cards",
+                    default: Value::Number(200),
+                    help: "number of cards",
+                },
+                Param {
+                    name: "winning",
+                    default: Value::Number(10),
+                    help: "winning numbers per card, at most 100",
+                },
+                Param {
+                    name: "yours",
+                    default: Value::Number(25),
+                    help: "numbers you have per card",
+                },
+                Param {
+                    name: "max",
+                    default: Value::Number(99),
+                    help: "largest number on a card",
+                },
+            ],
+            generate: day04,
+        },
+        Generator {
+            year: 2023,
+            day: 5,
+            params: &[
+                Param {
+                    name: "maps",
+                    default: Value::Number(7),
+                    help: "maps from seed to location, 1 to 7",
+                },
+                Param {
+                    name: "ranges",
+                    default: Value::Number(30),
+                    help: "ranges in each map",
+                },
+                Param {
+                    name: "seeds",
+                    default: Value::Number(10),
+                    help: "number of seed ranges",
+                },
+                Param {
+                    name: "size",
+                    default: Value::Number(1_000_000),
+                    help: "largest length of a seed range",
+                },
+                Param {
+                    name: "space",
+                    default: Value::Number(1 << 32),
+                    help: "all values stay below this",
+                },
+            ],
+            generate: day05,
+        },
+        Generator {
+            year: 2023,
+            day: 6,
+            params: &[
+                Param {
+                    name: "races",
+                    default: Value::Number(4),
+                    help: "number of races",
+                },
+                Param {
+                    name: "time",
+                    default: Value::Number(100),
+                    help: "longest race time",
+                },
+            ],
+            generate: day06,
+        },
+    ];
+}
+
+pub fn find(year: u16, day: u8) -> Option<Generator> {
+    return all()
+        .into_iter()
+        .find(|generator| generator.year == year && generator.day == day);
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn letters(rng: &mut ChaCha8Rng, most: u64) -> String {
+    let count = rng.gen_range(0..=most);
+    return (0..count).map(|_| rng.gen_range('a'..='z')).collect();
+}
+
+// lines of letters with digits in between, some of them spelled out; every
+// line has at least one real digit so part one has an answer too
+fn day01(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let mut lines = Vec::new();
+    for _ in 0..params.number("lines") {
+        let tokens = rng.gen_range(1..=4);
+        let digit = rng.gen_range(0..tokens);
+        let mut line = String::new();
+        for token in 0..tokens {
+            line.push_str(&letters(rng, params.number("filler")));
+            let value = rng.gen_range(1..=9);
+            if token != digit && rng.gen_range(0..100) < params.number("words") {
+                line.push_str(DIGIT_WORDS[value - 1]);
+            } else {
+                line.push_str(&value.to_string());
+            }
+        }
+        line.push_str(&letters(rng, params.number("filler")));
+        lines.push(line);
+    }
+
+    let part1: u64 = lines
+        .iter()
+        .map(|line| {
+            let digits = line
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .collect::<Vec<_>>();
+            return (digits[0] * 10 + digits[digits.len() - 1]) as u64;
+        })
+        .sum();
+
+    // where each digit first and last appears, in any of its spellings
+    let part2: u64 = lines
+        .iter()
+        .map(|line| {
+            let mut first = (usize::MAX, 0);
+            let mut last = (0, 0);
+            for value in 1..=9 {
+                for token in [value.to_string(), DIGIT_WORDS[value - 1].to_string()] {
+                    if let Some(i) = line.find(&token) {
+                        first = first.min((i, value));
+                    }
+                    if let Some(i) = line.rfind(&token) {
+                        last = last.max((i, value));
+                    }
+                }
+            }
+            return (first.1 * 10 + last.1) as u64;
+        })
+        .sum();
+
+    return Generated {
+        input: lines.join("\n") + "\n",
+        answers: vec![(1, part1.to_string()), (2, part2.to_string())],
+    };
+}
+
+// the bag the runner asks day two about
+const BAG: [(&str, u64); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+fn day02(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let mut lines = Vec::new();
+    let mut possible = 0;
+    for id in 1..=params.number("games") {
+        let mut fits = true;
+        let mut handfuls = Vec::new();
+        for _ in 0..rng.gen_range(1..=params.number("handfuls").max(1)) {
+            let mut colors = BAG.to_vec();
+            colors.shuffle(rng);
+            colors.truncate(rng.gen_range(1..=3));
+
+            let mut counts = Vec::new();
+            for (color, limit) in colors {
+                let count = rng.gen_range(1..=params.number("cubes").max(1));
+                fits &= count <= limit;
+                counts.push(format!("{} {}", count, color));
+            }
+            handfuls.push(counts.join(", "));
+        }
+
+        if fits {
+            possible += id;
+        }
+        lines.push(format!("Game {}: {}", id, handfuls.join("; ")));
+    }
+
+    return Generated {
+        input: lines.join("\n") + "\n",
+        answers: vec![(1, possible.to_string())],
+    };
+}
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'];
+
+// numbers of one to three digits, never touching another number on the same
+// line, and symbols scattered in between
+fn day03(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let width = params.number("width").max(1) as usize;
+    let mut grid = Vec::new();
+    for _ in 0..params.number("height").max(1) {
+        let mut row = Vec::new();
+        while row.len() < width {
+            let roll = rng.gen_range(0..100);
+            if roll < params.number("numbers") {
+                let length = rng.gen_range(1..=3).min(width - row.len());
+                row.push(rng.gen_range('1'..='9'));
+                for _ in 1..length {
+                    row.push(rng.gen_range('0'..='9'));
+                }
+                if row.len() < width {
+                    row.push('.');
+                }
+            } else if roll < params.number("numbers") + params.number("symbols") {
+                row.push(*SYMBOLS.choose(rng).unwrap());
+            } else {
+                row.push('.');
+            }
+        }
+        grid.push(row);
+    }
+
+    let is_symbol = |y: i64, x: i64| {
+        if y < 0 || x < 0 || y as usize >= grid.len() || x as usize >= width {
+            return false;
+        }
+        let c = grid[y as usize][x as usize];
+        return !c.is_ascii_digit() && c != '.';
+    };
+
+    let mut part1 = 0;
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < width {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < width && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            let y = y as i64;
+            let touches = (start as i64 - 1..=x as i64)
+                .any(|nx| is_symbol(y - 1, nx) || is_symbol(y, nx) || is_symbol(y + 1, nx));
+            if touches {
+                part1 += row[start..x]
+                    .iter()
+                    .collect::<String>()
+                    .parse::<u64>()
+                    .unwrap();
+            }
+        }
+    }
+
+    let input = grid
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    return Generated {
+        input: input.join("\n") + "\n",
+        answers: vec![(1, part1.to_string())],
+    };
+}
+
+// distinct numbers from 1 to max, aligned in columns like the real cards
+fn day04(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let max = params.number("max").max(1) as usize;
+    let cards = params.number("cards");
+    let winning_count = (params.number("winning") as usize).min(100).min(max);
+    let your_count = (params.number("yours") as usize).min(max);
+    let id_width = cards.to_string().len();
+    let number_width = max.to_string().len();
+
+    let mut lines = Vec::new();
+    // u64 like the solution, None once the points no longer fit
+    let mut points = Some(0u64);
+    for id in 1..=cards {
+        let mut draw = |count| {
+            return index::sample(rng, max, count)
+                .iter()
+                .map(|n| n + 1)
+                .collect::<Vec<_>>();
+        };
+        let winning = draw(winning_count);
+        let yours = draw(your_count);
+
+        let matches = yours.iter().filter(|n| winning.contains(n)).count();
+        if matches > 0 {
+            let worth = 1u64.checked_shl(matches as u32 - 1);
+            points = points
+                .zip(worth)
+                .and_then(|(sum, worth)| sum.checked_add(worth));
+        }
+
+        let column = |numbers: &[usize]| {
+            return numbers
+                .iter()
+                .map(|n| format!("{:>width$}", n, width = number_width))
+                .collect::<Vec<_>>()
+                .join(" ");
+        };
+        lines.push(format!(
+            "Card {:>width$}: {} | {}",
+            id,
+            column(&winning),
+            column(&yours),
+            width = id_width
+        ));
+    }
+
+    return Generated {
+        input: lines.join("\n") + "\n",
+        answers: points
+            .map(|points| (1, points.to_string()))
+            .into_iter()
+            .collect(),
+    };
+}
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// (destination, source, length), with sources that never overlap so the
+// order of the lines does not matter
+type Mapping = (u64, u64, u64);
+
+fn day05(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let space = params.number("space").max(2);
+    let size = params.number("size").clamp(1, space - 1);
+
+    let mut maps: Vec<Vec<Mapping>> = Vec::new();
+    for _ in 0..params.number("maps").clamp(1, 7) {
+        let mut starts = (0..params.number("ranges").max(1))
+            .map(|_| rng.gen_range(0..space))
+            .collect::<Vec<_>>();
+        starts.sort();
+        starts.dedup();
+
+        let mut map = Vec::new();
+        for (i, &src) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(space);
+            let len = rng.gen_range(1..=end - src);
+            let dst = rng.gen_range(0..=space - len);
+            map.push((dst, src, len));
+        }
+        map.shuffle(rng);
+        maps.push(map);
+    }
+
+    let seeds = (0..params.number("seeds").max(1))
+        .map(|_| (rng.gen_range(0..space - size), rng.gen_range(1..=size)))
+        .collect::<Vec<_>>();
+
+    let mut sections = vec![format!(
+        "seeds: {}",
+        seeds
+            .iter()
+            .map(|(start, len)| format!("{} {}", start, len))
+            .collect::<Vec<_>>()
+            .join(" ")
+    )];
+    let mut names = CATEGORIES[..maps.len()].to_vec();
+    names.push("location");
+    for (i, map) in maps.iter().enumerate() {
+        let mut section = format!("{}-to-{} map:", names[i], names[i + 1]);
+        for (dst, src, len) in map {
+            section.push_str(&format!("\n{} {} {}", dst, src, len));
+        }
+        sections.push(section);
+    }
+
+    // part one treats every number on the seeds line as a seed
+    let part1 = seeds
+        .iter()
+        .flat_map(|&(start, len)| [start, len])
+        .map(|seed| {
+            let intervals = maps.iter().fold(vec![(seed, seed + 1)], |intervals, map| {
+                return map_intervals(map, &intervals);
+            });
+            return intervals[0].0;
+        })
+        .min()
+        .unwrap();
+
+    // part two follows whole intervals through the maps instead of every seed
+    let mut intervals = seeds
+        .iter()
+        .map(|&(start, len)| (start, start + len))
+        .collect::<Vec<_>>();
+    for map in &maps {
+        intervals = map_intervals(map, &intervals);
+    }
+    let part2 = intervals.iter().map(|(start, _)| *start).min().unwrap();
+
+    return Generated {
+        input: sections.join("\n\n") + "\n",
+        answers: vec![(1, part1.to_string()), (2, part2.to_string())],
+    };
+}
+
+// the half-open intervals covering where `intervals` end up through `map`
+fn map_intervals(map: &[Mapping], intervals: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut mapped = Vec::new();
+    for &(start, end) in intervals {
+        let mut current = start;
+        while current < end {
+            let range = map
+                .iter()
+                .find(|(_, src, len)| *src <= current && current < src + len);
+            match range {
+                Some((dst, src, len)) => {
+                    let until = end.min(src + len);
+                    mapped.push((dst + current - src, dst + until - src));
+                    current = until;
+                }
+                None => {
+                    // unmapped values keep their number up to the next range
+                    let until = map
+                        .iter()
+                        .map(|(_, src, _)| *src)
+                        .filter(|src| *src > current)
+                        .min()
+                        .unwrap_or(end)
+                        .min(end);
+                    mapped.push((current, until));
+                    current = until;
+                }
+            }
+        }
+    }
+
+    return mapped;
+}
+
+fn day06(rng: &mut ChaCha8Rng, params: &Params) -> Generated {
+    let races = (0..params.number("races").max(1))
+        .map(|_| {
+            let time = rng.gen_range(1..=params.number("time").max(1)) as u128;
+            let best = (time / 2) * (time - time / 2);
+            return (time, rng.gen_range(0..best.max(1)));
+        })
+        .collect::<Vec<_>>();
+
+    let width = races
+        .iter()
+        .map(|(time, record)| time.to_string().len().max(record.to_string().len()))
+        .max()
+        .unwrap();
+    let row = |values: Vec<u128>| {
+        return values
+            .iter()
+            .map(|v| format!("{:>width$}", v, width = width + 3))
+            .collect::<String>();
+    };
+    let input = format!(
+        "Time:    {}\nDistance:{}\n",
+        row(races.iter().map(|r| r.0).collect()),
+        row(races.iter().map(|r| r.1).collect())
+    );
+
+    // the product and the joined race are None where they pass a u128, like
+    // the solution, which gives an error for them
+    let part1 = races
+        .iter()
+        .map(|&(time, record)| ways_to_win(time, record))
+        .try_fold(1u128, |product, ways| product.checked_mul(ways));
+    let joined = |values: Vec<u128>| {
+        return values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<String>()
+            .parse::<u128>()
+            .ok();
+    };
+    let part2 = joined(races.iter().map(|r| r.0).collect())
+        .zip(joined(races.iter().map(|r| r.1).collect()))
+        .map(|(time, record)| ways_to_win(time, record));
+
+    let answers = [(1, part1), (2, part2)]
+        .into_iter()
+        .filter_map(|(part, answer)| Some((part, answer?.to_string())))
+        .collect();
+    return Generated { input, answers };
+}
+
+// the distance grows up to holding for half the race, so the first hold that
+// beats the record is found by bisection and the rest mirror it; distances
+// saturate like the solution's do, a joined race can be that long
+fn ways_to_win(time: u128, record: u128) -> u128 {
+    let half = time / 2;
+    if half.saturating_mul(time - half) <= record {
+        return 0;
+    }
+
+    let (mut low, mut high) = (0, half);
+    while low < high {
+        let mid = (low + high) / 2;
+        if mid.saturating_mul(time - mid) > record {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    return time - 2 * low + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn params(values: &[&str]) -> Vec<String> {
+        return values.iter().map(|v| v.to_string()).collect();
+    }
+
+    // small inputs, so the real solutions can check the reference ones
+    fn small(day: u8) -> Vec<String> {
+        return match day {
+            1 => params(&["lines=50"]),
+            2 => params(&["games=30"]),
+            3 => params(&["width=30", "height=20", "symbols=10"]),
+            4 => params(&["cards=30", "winning=5", "yours=8", "max=20"]),
+            5 => params(&["maps=4", "ranges=5", "seeds=3", "size=200", "space=5000"]),
+            _ => params(&["races=3", "time=60"]),
+        };
+    }
+
+    #[test]
+    fn test_answers_match_solutions() {
+        for generator in all() {
+            let day = days::find(generator.year, generator.day).unwrap();
+            for seed in 0..5 {
+                let generated = generator.generate(seed, &small(generator.day)).unwrap();
+                for (part, answer) in generated.answers {
+                    let solver = day.solver(part).unwrap();
+                    assert_eq!(
+                        solver(
+                            &generated.input,
+                            &crate::params::Params::defaults(day.params),
+                            &progress::Progress::new()
+                        ),
+                        Ok(answer),
+                        "day {} part {} with seed {}",
+                        generator.day,
+                        part,
+                        seed
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let generator = find(2023, 3).unwrap();
+        let first = generator.generate(7, &small(3)).unwrap().input;
+        assert_eq!(generator.generate(7, &small(3)).unwrap().input, first);
+        assert_ne!(generator.generate(8, &small(3)).unwrap().input, first);
+    }
+
+    #[test]
+    fn test_params() {
+        let generator = find(2023, 3).unwrap();
+        let input = generator
+            .generate(1, &params(&["width=12", "height=3"]))
+            .unwrap()
+            .input;
+        assert_eq!(
+            input.lines().map(|l| l.len()).collect::<Vec<_>>(),
+            [12, 12, 12]
+        );
+
+        let error = generator.generate(1, &params(&["depth=3"])).err().unwrap();
+        assert!(error.contains("no parameter depth"));
+        assert!(error.contains("width=140"));
+        assert!(generator.generate(1, &params(&["width"])).is_err());
+        assert!(generator.generate(1, &params(&["width=wide"])).is_err());
+    }
+
+    #[test]
+    fn test_map_intervals() {
+        let map = [(50, 98, 2), (52, 50, 48)];
+        assert_eq!(
+            map_intervals(&map, &[(79, 93), (45, 52), (99, 101)]),
+            [(81, 95), (45, 50), (52, 54), (51, 52), (100, 101)]
+        );
+    }
+
+    #[test]
+    fn test_ways_to_win() {
+        assert_eq!(ways_to_win(7, 9), 4);
+        assert_eq!(ways_to_win(15, 40), 8);
+        assert_eq!(ways_to_win(30, 200), 9);
+        assert_eq!(ways_to_win(71530, 940200), 71503);
+        assert_eq!(ways_to_win(4, 4), 0);
+        // holding 2 to MAX - 2 goes past a u128
+        assert_eq!(ways_to_win(u128::MAX, u128::MAX - 1), u128::MAX - 3);
+    }
+
+    #[test]
+    fn test_answers_too_big() {
+        let parts = |day, values: &[&str]| {
+            let generated = find(2023, day)
+                .unwrap()
+                .generate(1, &params(values))
+                .unwrap();
+            return generated.answers.iter().map(|a| a.0).collect::<Vec<_>>();
+        };
+        // the joined race has far more digits than a u128
+        assert_eq!(parts(6, &["races=20"]), [1]);
+        // four races of up to 2^64, with as many ways to win each
+        assert_eq!(
+            parts(6, &["races=4", "time=18446744073709551615"]),
+            Vec::<u8>::new()
+        );
+        assert_eq!(parts(6, &["races=2", "time=18446744073709551615"]), [1]);
+        // every card has all 64 numbers, 2^63 points each
+        let all = ["cards=2", "winning=64", "yours=64", "max=64"];
+        assert_eq!(parts(4, &all), Vec::<u8>::new());
+        assert_eq!(
+            parts(4, &["cards=1", "winning=64", "yours=64", "max=64"]),
+            [1]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::submit::Outcome;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    // unix time of the submission, in seconds
+    pub time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+// every answer ever submitted, so the same mistake is not sent twice
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, String> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        return toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|e| e.to_string())?;
+        return fs::write(path, text)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e));
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        return self
+            .submissions
+            .iter()
+            .filter(move |s| s.day == day && s.part == part);
+    }
+
+    // returns why the answer should not be submitted, if there is a reason
+    pub fn reject(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        if let Some(correct) = self
+            .for_part(day, part)
+            .find(|s| s.outcome == Outcome::Correct)
+        {
+            return Some(format!("already solved, the answer was {}", correct.answer));
+        }
+
+        if let Some(cooldown) = self
+            .for_part(day, part)
+            .filter_map(|s| s.wait.map(|wait| s.time + wait))
+            .max()
+            .filter(|&until| until > now)
+        {
+            return Some(format!("wait {} more seconds", cooldown - now));
+        }
+
+        if let Some(previous) = self
+            .for_part(day, part)
+            .find(|s| s.answer == answer && is_wrong(s.outcome))
+        {
+            return Some(format!(
+                "{} was already rejected as {:?}",
+                answer, previous.outcome
+            ));
+        }
+
+        // the bounds only make sense for numbers
+        let value = answer.parse::<i128>().ok()?;
+        let bound = |outcome| {
+            self.for_part(day, part)
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(high) = bound(Outcome::TooHigh).min().filter(|&high| value >= high) {
+            return Some(format!("{} is too high, {} already was", answer, high));
+        }
+        if let Some(low) = bound(Outcome::TooLow).max().filter(|&low| value <= low) {
+            return Some(format!("{} is too low, {} already was", answer, low));
+        }
+
+        return None;
+    }
+}
+
+fn is_wrong(outcome: Outcome) -> bool {
+    return matches!(outcome, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(answer: &str, outcome: Outcome, time: u64, wait: Option<u64>) -> Submission {
+        return Submission {
+            day: 5,
+            part: 2,
+            answer: answer.to_string(),
+            outcome,
+            time,
+            wait,
+        };
+    }
+
+    fn history(submissions: Vec<Submission>) -> History {
+        return History { submissions };
+    }
+
+    #[test]
+    fn test_reject_known_wrong() {
+        let history = history(vec![submission("40", Outcome::Wrong, 0, None)]);
+        assert!(history.reject(5, 2, "40", 100).is_some());
+        assert!(history.reject(5, 2, "41", 100).is_none());
+        // other parts are unaffected
+        assert!(history.reject(5, 1, "40", 100).is_none());
+    }
+
+    #[test]
+    fn test_reject_out_of_bounds() {
+        let history = history(vec![
+            submission("100", Outcome::TooHigh, 0, None),
+            submission("120", Outcome::TooHigh, 0, None),
+            submission("10", Outcome::TooLow, 0, None),
+        ]);
+        assert!(history.reject(5, 2, "100", 100).is_some());
+        assert!(history.reject(5, 2, "110", 100).is_some());
+        assert!(history.reject(5, 2, "10", 100).is_some());
+        assert!(history.reject(5, 2, "3", 100).is_some());
+        assert!(history.reject(5, 2, "46", 100).is_none());
+        assert!(history.reject(5, 2, "not a number", 100).is_none());
+    }
+
+    #[test]
+    fn test_reject_during_cooldown() {
+        let history = history(vec![submission("10", Outcome::TooLow, 1000, Some(60))]);
+        assert_eq!(
+            history.reject(5, 2, "46", 1030),
+            Some("wait 30 more seconds".to_string())
+        );
+        assert!(history.reject(5, 2, "46", 1060).is_none());
+    }
+
+    #[test]
+    fn test_reject_when_solved() {
+        let history = history(vec![submission("46", Outcome::Correct, 0, None)]);
+        assert!(history.reject(5, 2, "46", 100).is_some());
+        assert!(history.reject(5, 2, "47", 100).is_some());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let history = history(vec![
+            submission("10", Outcome::TooLow, 1000, Some(60)),
+            submission("46", Outcome::Correct, 2000, None),
+        ]);
+        let text = toml::to_string(&history).unwrap();
+        assert_eq!(toml::from_str::<History>(&text).unwrap(), history);
+    }
+}
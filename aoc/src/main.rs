@@ -0,0 +1,741 @@
+mod answers;
+mod bench;
+mod budget;
+mod days;
+mod encrypt;
+mod extract;
+mod generate;
+mod history;
+mod memory;
+mod parallel;
+mod params;
+mod report;
+mod scaffold;
+mod submit;
+mod trace;
+mod watch;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use answers::Answers;
+use bench::Baseline;
+use clap::{Parser, Subcommand};
+use days::Day;
+use history::{History, Submission};
+use params::{Config, Params};
+use progress::Progress;
+use report::{Format, Record, Verdict};
+use submit::{Client, Outcome};
+use tracing::info_span;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Run and check the Advent of Code solutions")]
+struct Cli {
+    /// Directory containing a directory of dayNN crates per year
+    #[arg(long, global = true, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/.."))]
+    root: PathBuf,
+
+    /// The year of the puzzles; its directory holds their crates, inputs, answers and history
+    #[arg(long, global = true, env = "AOC_YEAR", default_value_t = 2023)]
+    year: u16,
+
+    /// Answers file, defaults to answers.toml in the year's directory
+    #[arg(long, global = true)]
+    answers: Option<PathBuf>,
+
+    /// Log more of what the solutions do, up to -vvv; AOC_LOG=y2023_day05=trace picks per crate
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Write the time spent in each day, part and parse step as folded stacks
+    #[arg(long, global = true, value_name = "PATH")]
+    flamegraph: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the puzzles and show how each answer compares to the known ones
+    Run {
+        #[arg(long)]
+        day: Option<u8>,
+        #[arg(long)]
+        part: Option<u8>,
+        /// Solve every day and part of the year at once, then show them all in a table
+        #[arg(long, conflicts_with_all = ["day", "part"])]
+        all: bool,
+        /// json writes one object per line as each part finishes
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Give up on a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Change a constant of the puzzle, like --param red=20; an unknown name lists them all
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// TOML file with a table of parameters per day, like [day02] red = 20
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+    },
+    /// Like run, but fail if any answer differs from the known one
+    Check {
+        #[arg(long)]
+        day: Option<u8>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// Create the crate for a new day from the template
+    New { day: u8 },
+    /// Save the examples of a downloaded puzzle page as test fixtures
+    Extract {
+        #[arg(long)]
+        day: u8,
+        /// The puzzle description, saved as HTML
+        page: PathBuf,
+    },
+    /// Time the parsing and each part of the solutions; the parts parse the input again, so their times include the parse step's
+    Bench {
+        #[arg(long)]
+        day: Option<u8>,
+        /// How many times each step is run
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        /// Baseline file, defaults to benchmarks.toml in the year's directory
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Store these timings as the new baseline
+        #[arg(long)]
+        save: bool,
+        /// Also count the allocations, bytes allocated and peak memory of each
+        /// step; needs aoc built with `--features memory`, which counts on a
+        /// run of its own so the timings are not slowed down
+        #[arg(long)]
+        memory: bool,
+    },
+    /// Solve a part and submit the answer to the Advent of Code website
+    Submit {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: u8,
+        /// Session cookie of the logged in user
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: String,
+        #[arg(long, env = "AOC_ENDPOINT", default_value = submit::DEFAULT_ENDPOINT)]
+        endpoint: String,
+        /// Submission history, defaults to submissions.toml in the year's directory
+        #[arg(long)]
+        history: Option<PathBuf>,
+    },
+    /// Make up a puzzle input of any size, with the answers a reference solution gives
+    Generate {
+        #[arg(long)]
+        day: u8,
+        /// The same seed and parameters always give the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Tune the input, like --param width=500; an unknown name lists them all
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+        /// Where to write the input, instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Rebuild, test and solve a day again whenever its sources, input or fixtures change
+    Watch {
+        #[arg(long)]
+        day: u8,
+    },
+    /// Draw how a solution sees its input, for the days that have a renderer
+    Render {
+        #[arg(long)]
+        day: u8,
+        /// Render this file instead of the day's input, like a fixture
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Write a standalone HTML (day 3) or SVG (day 5) file there, instead of drawing in the terminal
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// The seed range to follow through the day 5 maps, defaults to the first in the input
+        #[arg(long, value_name = "START:LEN")]
+        seeds: Option<String>,
+    },
+    /// Encrypt the plain src/input.txt of the days so only the encrypted copy is committed
+    ///
+    /// The key comes from AOC_INPUT_KEY, the file AOC_INPUT_KEYFILE names, or
+    /// .input-key in the root, which is created with a random key if there is
+    /// none anywhere yet
+    Encrypt {
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Explore the day 5 almanac one command at a time, like map seed 79 location
+    Repl {
+        /// Load this file instead of the day's input, like a fixture
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    // writes the flamegraph once main is done
+    let _flame = match trace::init(cli.verbose, cli.flamegraph.as_deref()) {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    return match run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    };
+}
+
+fn run(cli: Cli) -> Result<ExitCode, String> {
+    // what is kept about the puzzles of a year, next to their crates
+    let year_dir = cli.root.join(cli.year.to_string());
+    let answers = cli.answers.unwrap_or(year_dir.join("answers.toml"));
+
+    return match cli.command {
+        Command::Run {
+            all: true,
+            format,
+            timeout,
+            params,
+            config,
+            ..
+        } => solve_all(
+            &cli.root,
+            &answers,
+            with_params(select_days(cli.year, None)?, config.as_deref(), &params)?,
+            format,
+            timeout,
+        ),
+        Command::Run {
+            day,
+            part,
+            format,
+            timeout,
+            params,
+            config,
+            ..
+        } => solve(
+            &cli.root,
+            &answers,
+            with_params(select_days(cli.year, day)?, config.as_deref(), &params)?,
+            part,
+            false,
+            format,
+            timeout,
+        ),
+        Command::Check {
+            day,
+            format,
+            timeout,
+        } => solve(
+            &cli.root,
+            &answers,
+            with_params(select_days(cli.year, day)?, None, &[])?,
+            None,
+            true,
+            format,
+            timeout,
+        ),
+        Command::New { day } => {
+            for path in scaffold::create(&cli.root, cli.year, day)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Extract { day, page } => {
+            let html = fs::read_to_string(&page)
+                .map_err(|e| format!("cannot read {}: {}", page.display(), e))?;
+            let examples = extract::extract(&html);
+            if examples.is_empty() {
+                return Err(format!("no examples found in {}", page.display()));
+            }
+
+            let dir = days::dir(&cli.root, cli.year, day).join("fixtures");
+            for path in extract::write_fixtures(&dir, &examples)? {
+                println!("wrote {}", path.display());
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Bench {
+            day,
+            runs,
+            baseline,
+            save,
+            memory,
+        } => {
+            let baseline = baseline.unwrap_or(year_dir.join("benchmarks.toml"));
+            benchmark(
+                &cli.root,
+                &baseline,
+                select_days(cli.year, day)?,
+                runs,
+                save,
+                memory,
+            )
+        }
+        Command::Submit {
+            day,
+            part,
+            session,
+            endpoint,
+            history,
+        } => {
+            let client = Client {
+                endpoint,
+                session,
+                year: cli.year,
+            };
+            let history = history.unwrap_or(year_dir.join("submissions.toml"));
+            submit(&cli.root, &client, &history, find_day(cli.year, day)?, part)
+        }
+        Command::Generate {
+            day,
+            seed,
+            params,
+            output,
+        } => generate_input(cli.year, day, seed, &params, output.as_deref()),
+        Command::Watch { day } => {
+            watch::watch(&cli.root, &answers, &find_day(cli.year, day)?)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Render {
+            day,
+            input,
+            output,
+            seeds,
+        } => render(
+            &cli.root,
+            cli.year,
+            day,
+            input.as_deref(),
+            output.as_deref(),
+            seeds.as_deref(),
+        ),
+        Command::Encrypt { day } => encrypt_inputs(&cli.root, select_days(cli.year, day)?),
+        Command::Repl { input } => repl(&cli.root, cli.year, input.as_deref()),
+    };
+}
+
+fn solve(
+    root: &Path,
+    answers_path: &Path,
+    days: Vec<(Day, Params)>,
+    part: Option<u8>,
+    strict: bool,
+    format: Format,
+    timeout: Option<Duration>,
+) -> Result<ExitCode, String> {
+    let answers = Answers::load(answers_path)?;
+
+    let mut wrong = 0;
+    let mut unparsed = 0;
+    let mut timed_out = 0;
+    for (day, params) in days {
+        let _span = info_span!("day", day = day.number).entered();
+        let input: Arc<str> = read_input(root, &day)?.into();
+        let parse_ns = day
+            .parse
+            .map(|parse| report::time(|| info_span!("parse").in_scope(|| parse(&input))));
+
+        for (number, solver) in day.parts() {
+            if part.is_some_and(|p| p != number) {
+                continue;
+            }
+
+            // the known answers are for the puzzle as it is
+            let answers = (!params.is_tuned()).then_some(&answers);
+            let (input, params) = (input.clone(), params.clone());
+            let record = Record::solve(
+                day.number,
+                number,
+                move |progress| solver(&input, &params, progress),
+                parse_ns,
+                answers,
+                timeout,
+                true,
+            );
+            match (&record.verdict, format) {
+                // parse errors stay off stdout, unless it is read by a program
+                (Verdict::Error { .. }, Format::Text) => eprintln!("{}", record.render(format)),
+                _ => println!("{}", record.render(format)),
+            }
+
+            match record.verdict {
+                Verdict::Wrong { .. } => wrong += 1,
+                Verdict::Error { .. } => unparsed += 1,
+                Verdict::Timeout => timed_out += 1,
+                _ => {}
+            }
+        }
+    }
+
+    if unparsed > 0 || timed_out > 0 {
+        return Ok(ExitCode::FAILURE);
+    }
+    if strict && wrong > 0 {
+        eprintln!("{} answer(s) differ from {}", wrong, answers_path.display());
+        return Ok(ExitCode::FAILURE);
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+// like solve, but with the days and parts running side by side; their
+// records come once they are all done, in order
+fn solve_all(
+    root: &Path,
+    answers_path: &Path,
+    days: Vec<(Day, Params)>,
+    format: Format,
+    timeout: Option<Duration>,
+) -> Result<ExitCode, String> {
+    let answers = Answers::load(answers_path)?;
+    let start = Instant::now();
+    let records = parallel::solve_all(&days, |day| read_input(root, day), &answers, timeout)?;
+
+    match format {
+        Format::Text => println!("{}", parallel::summary(&records, start.elapsed())),
+        Format::Json => {
+            for record in &records {
+                println!("{}", record.render(format));
+            }
+        }
+    }
+
+    let failed = records.iter().any(|record| {
+        matches!(
+            record.verdict,
+            Verdict::Error { .. } | Verdict::Timeout | Verdict::Panic { .. }
+        )
+    });
+    if failed {
+        return Ok(ExitCode::FAILURE);
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+fn benchmark(
+    root: &Path,
+    baseline_path: &Path,
+    days: Vec<Day>,
+    runs: usize,
+    save: bool,
+    memory: bool,
+) -> Result<ExitCode, String> {
+    if runs == 0 {
+        return Err("need at least one run".to_string());
+    }
+    if memory && !memory::ENABLED {
+        return Err(
+            "counting allocations needs aoc built with the memory feature, like cargo run -p aoc --features memory -- bench --memory"
+                .to_string(),
+        );
+    }
+
+    let mut baseline = Baseline::load(baseline_path)?;
+    let mut measurements = Vec::new();
+    for day in days {
+        let input = read_input(root, &day)?;
+        measurements.extend(bench::bench_day(&day, &input, runs, memory));
+    }
+
+    println!("{}", bench::table(&measurements, &baseline));
+
+    if save {
+        baseline.save(baseline_path, &measurements)?;
+        println!("saved baseline to {}", baseline_path.display());
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+fn submit(
+    root: &Path,
+    client: &Client,
+    history_path: &Path,
+    day: Day,
+    part: u8,
+) -> Result<ExitCode, String> {
+    let solver = day
+        .solver(part)
+        .ok_or(format!("{} has no part {}", day.name(), part))?;
+    let params = Params::defaults(day.params);
+    let answer =
+        solver(&read_input(root, &day)?, &params, &Progress::new()).map_err(|e| e.to_string())?;
+
+    let mut history = History::load(history_path)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+
+    if let Some(reason) = history.reject(day.number, part, &answer, now) {
+        eprintln!("not submitting {}: {}", answer, reason);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let response = client.submit(day.number, part, &answer)?;
+    println!(
+        "{} part {}: {} ({:?})",
+        day.name(),
+        part,
+        answer,
+        response.outcome
+    );
+    if let Some(wait) = response.wait {
+        println!("next submission possible in {} seconds", wait);
+    }
+
+    history.record(Submission {
+        day: day.number,
+        part,
+        answer,
+        outcome: response.outcome,
+        time: now,
+        wait: response.wait,
+    });
+    history.save(history_path)?;
+
+    return Ok(match response.outcome {
+        Outcome::Correct => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    });
+}
+
+fn generate_input(
+    year: u16,
+    day: u8,
+    seed: u64,
+    params: &[String],
+    output: Option<&Path>,
+) -> Result<ExitCode, String> {
+    let generator =
+        generate::find(year, day).ok_or(format!("no generator for day {} of {}", day, year))?;
+    let generated = generator.generate(seed, params)?;
+
+    match output {
+        Some(path) => {
+            fs::write(path, &generated.input)
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+            println!("wrote {}", path.display());
+        }
+        None => print!("{}", generated.input),
+    }
+    // the input may be going to stdout, keep the answers apart from it
+    for (part, answer) in &generated.answers {
+        eprintln!("day{:02} part {}: {} (reference)", day, part, answer);
+    }
+    let parts = days::find(year, day).map_or(vec![], |day| day.parts());
+    for (part, _) in parts {
+        if generated
+            .answers
+            .iter()
+            .all(|(answered, _)| *answered != part)
+        {
+            eprintln!(
+                "day{:02} part {}: too big to solve, no reference",
+                day, part
+            );
+        }
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+fn render(
+    root: &Path,
+    year: u16,
+    day: u8,
+    input: Option<&Path>,
+    output: Option<&Path>,
+    seeds: Option<&str>,
+) -> Result<ExitCode, String> {
+    if year != 2023 || (day != 3 && day != 5) {
+        return Err(format!("no renderer for day {} of {}", day, year));
+    }
+    let seeds = seeds.map(parse_seed_range).transpose()?;
+    let input = read_input_or(root, year, day, input)?;
+
+    let drawing = match (day, output) {
+        (3, Some(_)) => y2023_day03::render::html(&input),
+        (3, None) => y2023_day03::render::ansi(&input),
+        (_, Some(_)) => y2023_day05::render::svg(&input, seeds).map_err(|e| e.to_string())?,
+        (_, None) => y2023_day05::render::ascii(&input, seeds).map_err(|e| e.to_string())?,
+    };
+    match output {
+        Some(path) => {
+            fs::write(path, drawing)
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+            println!("wrote {}", path.display());
+        }
+        None => print!("{}", drawing),
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+fn encrypt_inputs(root: &Path, days: Vec<Day>) -> Result<ExitCode, String> {
+    let (key, created) = encrypt::key(root)?;
+    if let Some(keyfile) = created {
+        println!(
+            "wrote a new key to {}, keep a copy of it: the inputs cannot be read without it",
+            keyfile.display()
+        );
+    }
+
+    let mut sealed = 0;
+    for day in days {
+        if let Some(path) = encrypt::seal(
+            &key,
+            &encrypt::plain_path(&day, root),
+            &day.input_path(root),
+        )? {
+            println!("encrypted {}", path.display());
+            sealed += 1;
+        }
+    }
+    if sealed == 0 {
+        println!("no src/input.txt to encrypt");
+    }
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+fn repl(root: &Path, year: u16, input: Option<&Path>) -> Result<ExitCode, String> {
+    if year != 2023 {
+        return Err(format!("the almanac is a puzzle of 2023, not {}", year));
+    }
+    let input = read_input_or(root, year, 5, input)?;
+    let almanac = y2023_day05::repl::Almanac::parse(&input).map_err(|e| e.to_string())?;
+
+    eprintln!("{}", y2023_day05::repl::HELP);
+    y2023_day05::repl::run(&almanac, std::io::stdin().lock(), std::io::stdout())
+        .map_err(|e| e.to_string())?;
+
+    return Ok(ExitCode::SUCCESS);
+}
+
+// a seed range written like 79:14
+fn parse_seed_range(text: &str) -> Result<(u64, u64), String> {
+    let invalid = || format!("invalid seed range {:?}, expected START:LEN", text);
+    let (start, len) = text.split_once(':').ok_or_else(invalid)?;
+    let start = start.trim().parse().map_err(|_| invalid())?;
+    let len = len.trim().parse().map_err(|_| invalid())?;
+
+    return Ok((start, len));
+}
+
+// a timeout in seconds, like 2 or 0.5
+fn parse_timeout(text: &str) -> Result<Duration, String> {
+    return text
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or(format!("invalid timeout {:?}, expected seconds", text));
+}
+
+// the given file, or the input of the day if there is none
+fn read_input_or(root: &Path, year: u16, day: u8, path: Option<&Path>) -> Result<String, String> {
+    return match path {
+        Some(path) => encrypt::read(root, path),
+        None => read_input(root, &find_day(year, day)?),
+    };
+}
+
+// the input of the day, decrypted
+fn read_input(root: &Path, day: &Day) -> Result<String, String> {
+    return encrypt::read(root, &day.input_path(root))
+        .map_err(|e| format!("cannot read input for {}: {}", day.name(), e));
+}
+
+// the parameters of each day, from the config file and then the command
+// line; a name on the command line has to belong to at least one of the days
+fn with_params(
+    days: Vec<Day>,
+    config: Option<&Path>,
+    overrides: &[String],
+) -> Result<Vec<(Day, Params)>, String> {
+    let config = match config {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    let overrides = params::parse_overrides(overrides)?;
+    for (name, _) in &overrides {
+        if !days
+            .iter()
+            .any(|day| day.params.iter().any(|p| p.name == name))
+        {
+            let known = days
+                .iter()
+                .filter(|day| !day.params.is_empty())
+                .map(|day| format!("{}:\n{}", day.name(), params::describe(day.params)))
+                .collect::<Vec<_>>();
+            return Err(format!(
+                "no parameter {}, there are:\n{}",
+                name,
+                known.join("\n")
+            ));
+        }
+    }
+
+    return days
+        .into_iter()
+        .map(|day| {
+            let params = Params::new(day.params, &config.day(day.number), &overrides)
+                .map_err(|e| format!("{}: {}", day.name(), e))?;
+            return Ok((day, params));
+        })
+        .collect();
+}
+
+fn find_day(year: u16, day: u8) -> Result<Day, String> {
+    return days::find(year, day).ok_or(format!(
+        "no solution registered for day {} of {}",
+        day, year
+    ));
+}
+
+// the given day, or every day of the year
+fn select_days(year: u16, day: Option<u8>) -> Result<Vec<Day>, String> {
+    if let Some(day) = day {
+        return Ok(vec![find_day(year, day)?]);
+    }
+
+    let days = days::all()
+        .into_iter()
+        .filter(|day| day.year == year)
+        .collect::<Vec<_>>();
+    if days.is_empty() {
+        let years = days::years()
+            .iter()
+            .map(|year| year.to_string())
+            .collect::<Vec<_>>();
+        return Err(format!(
+            "no solutions registered for {}, only for {}",
+            year,
+            years.join(", ")
+        ));
+    }
+
+    return Ok(days);
+}
@@ -0,0 +1,198 @@
+// without the memory feature the counting allocator is only there for its tests
+#![cfg_attr(not(feature = "memory"), allow(dead_code))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+// every allocation of the runner goes through here when it is built with the
+// memory feature; it only counts while something is being tracked
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting::new();
+
+pub const ENABLED: bool = cfg!(feature = "memory");
+
+// what a step allocated, on all threads, while it was tracked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    // the most it held on top of what was there when it started
+    pub peak_bytes: u64,
+}
+
+// the system allocator, with counters
+pub struct Counting {
+    tracking: AtomicBool,
+    allocations: AtomicU64,
+    allocated_bytes: AtomicU64,
+    // can go below zero, when memory from before the tracking is freed
+    current_bytes: AtomicI64,
+    peak_bytes: AtomicI64,
+}
+
+impl Counting {
+    pub const fn new() -> Counting {
+        return Counting {
+            tracking: AtomicBool::new(false),
+            allocations: AtomicU64::new(0),
+            allocated_bytes: AtomicU64::new(0),
+            current_bytes: AtomicI64::new(0),
+            peak_bytes: AtomicI64::new(0),
+        };
+    }
+
+    // runs `f` with the counters started from zero; only one step can be
+    // tracked at a time
+    pub fn track<T>(&self, f: impl FnOnce() -> T) -> (T, Usage) {
+        self.allocations.store(0, Ordering::Relaxed);
+        self.allocated_bytes.store(0, Ordering::Relaxed);
+        self.current_bytes.store(0, Ordering::Relaxed);
+        self.peak_bytes.store(0, Ordering::Relaxed);
+
+        self.tracking.store(true, Ordering::SeqCst);
+        let output = f();
+        self.tracking.store(false, Ordering::SeqCst);
+
+        let usage = Usage {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            allocated_bytes: self.allocated_bytes.load(Ordering::Relaxed),
+            peak_bytes: self.peak_bytes.load(Ordering::Relaxed).max(0) as u64,
+        };
+        return (output, usage);
+    }
+
+    fn allocated(&self, size: usize) {
+        if !self.tracking.load(Ordering::Relaxed) {
+            return;
+        }
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.allocated_bytes
+            .fetch_add(size as u64, Ordering::Relaxed);
+        let current = self.current_bytes.fetch_add(size as i64, Ordering::Relaxed) + size as i64;
+        self.peak_bytes.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn freed(&self, size: usize) {
+        if !self.tracking.load(Ordering::Relaxed) {
+            return;
+        }
+        self.current_bytes.fetch_sub(size as i64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        return ptr;
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        return ptr;
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.freed(layout.size());
+    }
+
+    // a grown or shrunk block counts as a new allocation of its new size
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            self.freed(layout.size());
+            self.allocated(new_size);
+        }
+        return new;
+    }
+}
+
+// what `f` allocates, counted by the runner's allocator; nothing without the
+// memory feature
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    #[cfg(feature = "memory")]
+    {
+        let (output, usage) = ALLOCATOR.track(f);
+        return (output, Some(usage));
+    }
+    #[cfg(not(feature = "memory"))]
+    return (f(), None);
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    return match bytes {
+        n if n < 1 << 10 => format!("{} B", n),
+        n if n < 1 << 20 => format!("{:.2} KiB", n as f64 / (1u64 << 10) as f64),
+        n if n < 1 << 30 => format!("{:.2} MiB", n as f64 / (1u64 << 20) as f64),
+        n => format!("{:.2} GiB", n as f64 / (1u64 << 30) as f64),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::hint::black_box;
+
+    #[test]
+    fn test_counting() {
+        let counting = Counting::new();
+        let layout = |size| Layout::from_size_align(size, 8).unwrap();
+        // nothing is counted outside of `track`
+        let before = unsafe { counting.alloc(layout(64)) };
+
+        let (_, usage) = counting.track(|| unsafe {
+            let a = counting.alloc(layout(100));
+            let b = counting.alloc_zeroed(layout(50));
+            counting.dealloc(a, layout(100));
+            let b = counting.realloc(b, layout(50), 200);
+            counting.dealloc(b, layout(200));
+            // freeing what was there before brings the peak no lower
+            counting.dealloc(before, layout(64));
+        });
+        assert_eq!(
+            usage,
+            Usage {
+                allocations: 3,
+                allocated_bytes: 350,
+                peak_bytes: 200,
+            }
+        );
+    }
+
+    #[test]
+    fn test_track() {
+        // other tests allocate at the same time, the counts are at least these
+        let (sum, usage) = track(|| {
+            let numbers = black_box((0..1000u64).collect::<Vec<_>>());
+            numbers.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 499500);
+        // only counted with the memory feature
+        if !ENABLED {
+            assert_eq!(usage, None);
+            return;
+        }
+        let usage = usage.unwrap();
+        assert!(usage.allocations >= 1);
+        assert!(usage.allocated_bytes >= 8000);
+        assert!(usage.peak_bytes >= 8000);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(5 << 20), "5.00 MiB");
+        assert_eq!(format_bytes(3 << 30), "3.00 GiB");
+    }
+}
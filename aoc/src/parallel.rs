@@ -0,0 +1,351 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Once};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use tracing::info_span;
+
+use crate::answers::Answers;
+use crate::bench::format_duration;
+use crate::days::Day;
+use crate::params::Params;
+use crate::report::{self, Record, Verdict};
+
+// solves every part of the days at once, the days and their parts spread
+// over a pool of threads, and gives the records back sorted by day and part.
+//
+// nothing is printed while they run, so the output of one part never ends
+// up in the middle of another's. A solution that panics, or a day whose input
+// cannot be read, becomes the record of its parts instead of ending the run
+pub fn solve_all(
+    days: &[(Day, Params)],
+    read: impl Fn(&Day) -> Result<String, String> + Sync,
+    answers: &Answers,
+    timeout: Option<Duration>,
+) -> Result<Vec<Record>, String> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .thread_name(|i| format!("aoc-{}", i))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut records = pool.install(|| {
+        return days
+            .par_iter()
+            .flat_map(|(day, params)| solve_day(day, params, &read, answers, timeout))
+            .collect::<Vec<_>>();
+    });
+
+    records.sort_by_key(|record| (record.day, record.part));
+    return Ok(records);
+}
+
+fn solve_day(
+    day: &Day,
+    params: &Params,
+    read: &(impl Fn(&Day) -> Result<String, String> + Sync),
+    answers: &Answers,
+    timeout: Option<Duration>,
+) -> Vec<Record> {
+    let _span = info_span!("day", day = day.number).entered();
+    let failed = |verdict: Verdict| {
+        return day
+            .parts()
+            .into_iter()
+            .map(|(part, _)| Record {
+                day: day.number,
+                part,
+                answer: None,
+                parse_ns: None,
+                solve_ns: 0,
+                verdict: verdict.clone(),
+            })
+            .collect::<Vec<_>>();
+    };
+
+    let input: Arc<str> = match read(day) {
+        Ok(input) => input.into(),
+        Err(error) => return failed(Verdict::Error { error }),
+    };
+    let parse_ns = match day.parse {
+        Some(parse) => match caught(|| report::time(|| parse(&input))) {
+            Ok(parse_ns) => Some(parse_ns),
+            Err(error) => return failed(Verdict::Panic { error }),
+        },
+        None => None,
+    };
+    // the known answers are for the puzzle as it is
+    let answers = (!params.is_tuned()).then_some(answers);
+
+    return day
+        .parts()
+        .into_par_iter()
+        .map(|(part, solver)| {
+            let start = Instant::now();
+            let (input, params) = (input.clone(), params.clone());
+            return caught(|| {
+                Record::solve(
+                    day.number,
+                    part,
+                    move |progress| quietly(|| solver(&input, &params, progress)),
+                    parse_ns,
+                    answers,
+                    timeout,
+                    false,
+                )
+            })
+            .unwrap_or_else(|error| Record {
+                day: day.number,
+                part,
+                answer: None,
+                parse_ns,
+                solve_ns: start.elapsed().as_nanos() as u64,
+                verdict: Verdict::Panic { error },
+            });
+        })
+        .collect();
+}
+
+thread_local! {
+    // whether a panic on this thread comes from a solution being run here
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// puts QUIET back the way it was, however `quietly` is left
+struct Quiet(bool);
+
+impl Drop for Quiet {
+    fn drop(&mut self) {
+        QUIET.with(|quiet| quiet.set(self.0));
+    }
+}
+
+// runs `f` without the panic hook printing what it panics with; the message
+// is kept in the record instead, the hook would print it wherever the other
+// parts are. Panics of anything else, on any thread, are left to the hook
+// there was
+fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    let _quiet = Quiet(QUIET.with(|quiet| quiet.replace(true)));
+    return f();
+}
+
+// runs `f`, or says what it panicked with
+fn caught<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    return quietly(|| panic::catch_unwind(AssertUnwindSafe(f))).map_err(|panic| message(&*panic));
+}
+
+fn message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = panic.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return "no message".to_string();
+}
+
+// one line per part, then what went wrong with the ones that failed
+pub fn summary(records: &[Record], elapsed: Duration) -> String {
+    let rows = records
+        .iter()
+        .map(|record| {
+            let status = match &record.verdict {
+                Verdict::Correct => "correct".to_string(),
+                Verdict::Wrong { expected } => format!("incorrect, expected {}", expected),
+                Verdict::Unknown => "unknown".to_string(),
+                Verdict::Error { .. } => "error".to_string(),
+                Verdict::Timeout => "timed out".to_string(),
+                Verdict::Panic { .. } => "panicked".to_string(),
+            };
+            [
+                format!("day{:02}", record.day),
+                record.part.to_string(),
+                record.answer.clone().unwrap_or("-".to_string()),
+                format_duration(record.solve_ns),
+                status,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![report::columns(
+        &["day", "part", "answer", "time", "status"],
+        &rows,
+    )];
+    for record in records {
+        if let Verdict::Error { error } | Verdict::Panic { error } = &record.verdict {
+            lines.push(format!(
+                "\nday{:02} part {}:\n{}",
+                record.day, record.part, error
+            ));
+        }
+    }
+    lines.push(format!(
+        "\n{} parts in {}",
+        records.len(),
+        format_duration(elapsed.as_nanos() as u64)
+    ));
+
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days;
+
+    fn answers() -> Answers {
+        return Answers::parse("[day01]\npart1 = 142\n[day06]\npart1 = 288\npart2 = 1").unwrap();
+    }
+
+    fn example(day: &Day) -> Result<String, String> {
+        return match day.number {
+            1 => Ok(include_str!("../../2023/day01/fixtures/example1.txt").to_string()),
+            6 => Ok(include_str!("../../2023/day06/fixtures/example1.txt").to_string()),
+            _ => Err(format!("no example for {}", day.name())),
+        };
+    }
+
+    fn with_defaults(days: Vec<Day>) -> Vec<(Day, Params)> {
+        return days
+            .into_iter()
+            .map(|day| {
+                let params = Params::defaults(day.params);
+                return (day, params);
+            })
+            .collect();
+    }
+
+    #[test]
+    fn test_solve_all() {
+        let days = with_defaults(vec![
+            days::find(2023, 6).unwrap(),
+            days::find(2023, 1).unwrap(),
+            days::find(2023, 3).unwrap(),
+        ]);
+        let records = solve_all(&days, example, &answers(), None).unwrap();
+
+        let verdicts = records
+            .iter()
+            .map(|r| (r.day, r.part, r.answer.as_deref(), &r.verdict))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verdicts,
+            [
+                (1, 1, Some("142"), &Verdict::Correct),
+                // the second example of day 1 is the one with spelled out digits
+                (1, 2, Some("142"), &Verdict::Unknown),
+                (
+                    3,
+                    1,
+                    None,
+                    &Verdict::Error {
+                        error: "no example for day03".to_string()
+                    }
+                ),
+                (6, 1, Some("288"), &Verdict::Correct),
+                (
+                    6,
+                    2,
+                    Some("71503"),
+                    &Verdict::Wrong {
+                        expected: "1".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_panic_is_a_failure() {
+        let mut day = days::find(2023, 6).unwrap();
+        day.part2 = Some(|_, _, _| panic!("boom"));
+        let days = with_defaults(vec![day, days::find(2023, 1).unwrap()]);
+        let records = solve_all(&days, example, &answers(), None).unwrap();
+
+        // the other part of the day, and the other day, still have their answers
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].verdict, Verdict::Correct);
+        assert_eq!(records[2].verdict, Verdict::Correct);
+        assert_eq!(
+            records[3].verdict,
+            Verdict::Panic {
+                error: "boom".to_string()
+            }
+        );
+
+        // a parser that panics fails both parts
+        let mut day = days::find(2023, 6).unwrap();
+        day.parse = Some(|_| panic!("{} is not a race", 7));
+        let records = solve_all(&with_defaults(vec![day]), example, &answers(), None).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.verdict
+            == Verdict::Panic {
+                error: "7 is not a race".to_string()
+            }));
+    }
+
+    #[test]
+    fn test_quietly() {
+        assert_eq!(caught(|| panic!("boom")), Err::<(), _>("boom".to_string()));
+        // only while the solution runs
+        assert!(!QUIET.with(Cell::get));
+        assert_eq!(caught(|| 7), Ok(7));
+    }
+
+    #[test]
+    fn test_summary() {
+        let record = |day, part, answer: Option<&str>, verdict| Record {
+            day,
+            part,
+            answer: answer.map(String::from),
+            parse_ns: None,
+            solve_ns: 1_500_000,
+            verdict,
+        };
+        let records = [
+            record(1, 1, Some("142"), Verdict::Correct),
+            record(
+                1,
+                2,
+                Some("281"),
+                Verdict::Wrong {
+                    expected: "280".to_string(),
+                },
+            ),
+            record(
+                5,
+                2,
+                None,
+                Verdict::Panic {
+                    error: "boom".to_string(),
+                },
+            ),
+        ];
+        assert_eq!(
+            summary(&records, Duration::from_millis(20)),
+            "\
+day    part  answer  time     status
+-----  ----  ------  -------  -----------------------
+day01  1     142     1.50 ms  correct
+day01  2     281     1.50 ms  incorrect, expected 280
+day05  2     -       1.50 ms  panicked
+
+day05 part 2:
+boom
+
+3 parts in 20.00 ms"
+        );
+    }
+}
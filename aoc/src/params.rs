@@ -0,0 +1,271 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::answers::parse_key;
+
+// what a parameter holds, with its default; the text of a default is
+// borrowed, so that the parameters can be declared in constants
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Number(u64),
+    Text(Cow<'static, str>),
+}
+
+impl Value {
+    // `value` read as the same kind of value as this one
+    fn parse(&self, name: &str, value: &str) -> Result<Value, String> {
+        let value = value.trim();
+        return match self {
+            Value::Number(_) => value
+                .parse()
+                .map(Value::Number)
+                .map_err(|_| format!("{} should be a whole number, got {}", name, value)),
+            Value::Text(_) => Ok(Value::Text(Cow::Owned(value.to_string()))),
+        };
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(s) => write!(f, "{}", s),
+        };
+    }
+}
+
+// a constant of a puzzle that a solution can be run with another value of,
+// or a knob of a generator
+#[derive(Debug, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: Value,
+    pub help: &'static str,
+}
+
+pub fn describe(params: &[Param]) -> String {
+    return params
+        .iter()
+        .map(|param| format!("  {}={}  {}", param.name, param.default, param.help))
+        .collect::<Vec<_>>()
+        .join("\n");
+}
+
+// the parameter called `name`, or the list of the ones there are
+pub fn find(declared: &'static [Param], name: &str) -> Result<&'static Param, String> {
+    return declared
+        .iter()
+        .find(|param| param.name == name)
+        .ok_or(format!(
+            "no parameter {}, there are:\n{}",
+            name,
+            describe(declared)
+        ));
+}
+
+// the values a day is solved with, checked against the type of each default
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    declared: &'static [Param],
+    values: BTreeMap<&'static str, Value>,
+}
+
+impl Params {
+    // the defaults, then the day's table of the config file, every name of
+    // which has to be declared, then the name=value pairs of the command line
+    // that the day has
+    pub fn new(
+        declared: &'static [Param],
+        config: &BTreeMap<String, String>,
+        overrides: &[(String, String)],
+    ) -> Result<Params, String> {
+        let mut params = Params {
+            declared,
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.clone()))
+                .collect(),
+        };
+
+        for (name, value) in config {
+            params.set(find(declared, name)?, value)?;
+        }
+        for (name, value) in overrides {
+            if let Some(param) = declared.iter().find(|param| param.name == name) {
+                params.set(param, value)?;
+            }
+        }
+
+        return Ok(params);
+    }
+
+    // the puzzle as it is
+    pub fn defaults(declared: &'static [Param]) -> Params {
+        return Params::new(declared, &BTreeMap::new(), &[]).unwrap();
+    }
+
+    fn set(&mut self, param: &'static Param, value: &str) -> Result<(), String> {
+        let value = param.default.parse(param.name, value)?;
+        self.values.insert(param.name, value);
+
+        return Ok(());
+    }
+
+    pub fn number(&self, name: &str) -> u64 {
+        return match &self.values[name] {
+            Value::Number(n) => *n,
+            Value::Text(_) => panic!("{} is not a number", name),
+        };
+    }
+
+    pub fn text(&self, name: &str) -> &str {
+        return match &self.values[name] {
+            Value::Text(text) => text,
+            Value::Number(_) => panic!("{} is not text", name),
+        };
+    }
+
+    // whether any value differs from its default, the known answers only
+    // hold for the puzzle as it is
+    pub fn is_tuned(&self) -> bool {
+        return self
+            .declared
+            .iter()
+            .any(|param| self.values[param.name] != param.default);
+    }
+}
+
+// name=value pairs, as given with --param
+pub fn parse_overrides(values: &[String]) -> Result<Vec<(String, String)>, String> {
+    return values
+        .iter()
+        .map(|value| {
+            let (name, value) = value
+                .split_once('=')
+                .ok_or(format!("expected name=value, got {}", value))?;
+            return Ok((name.trim().to_string(), value.trim().to_string()));
+        })
+        .collect();
+}
+
+// the parameters of each day, from a TOML file with one table per day:
+//
+// [day02]
+// red = 20
+//
+// [day05]
+// from = "soil"
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    days: BTreeMap<u8, BTreeMap<String, String>>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        return Config::parse(&text).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut days = BTreeMap::new();
+
+        for (day_key, values) in table.iter() {
+            let day = parse_key(day_key, "day")?;
+            let values = values
+                .as_table()
+                .ok_or(format!("[{}] should be a table", day_key))?;
+
+            let mut params = BTreeMap::new();
+            for (name, value) in values.iter() {
+                let value = match value {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Integer(i) => i.to_string(),
+                    _ => {
+                        return Err(format!(
+                            "{}.{} should be a string or an integer",
+                            day_key, name
+                        ))
+                    }
+                };
+                params.insert(name.clone(), value);
+            }
+            days.insert(day, params);
+        }
+
+        return Ok(Config { days });
+    }
+
+    pub fn day(&self, day: u8) -> BTreeMap<String, String> {
+        return self.days.get(&day).cloned().unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUBES: &[Param] = &[
+        Param {
+            name: "red",
+            default: Value::Number(12),
+            help: "red cubes",
+        },
+        Param {
+            name: "to",
+            default: Value::Text(Cow::Borrowed("location")),
+            help: "where to go",
+        },
+    ];
+
+    fn overrides(values: &[&str]) -> Vec<(String, String)> {
+        let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        return parse_overrides(&values).unwrap();
+    }
+
+    #[test]
+    fn test_defaults() {
+        let params = Params::defaults(CUBES);
+        assert_eq!(params.number("red"), 12);
+        assert_eq!(params.text("to"), "location");
+        assert!(!params.is_tuned());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let config = Config::parse("[day02]\nred = 15\nto = \"soil\"").unwrap();
+        let params = Params::new(CUBES, &config.day(2), &overrides(&["red=20", "blue=3"])).unwrap();
+        // the command line wins over the file, and names of other days are left alone
+        assert_eq!(params.number("red"), 20);
+        assert_eq!(params.text("to"), "soil");
+        assert!(params.is_tuned());
+
+        // the default given again changes nothing, however it is written
+        let params = Params::new(
+            CUBES,
+            &BTreeMap::new(),
+            &overrides(&["red=012", "to=location"]),
+        )
+        .unwrap();
+        assert!(!params.is_tuned());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = Params::new(CUBES, &BTreeMap::new(), &overrides(&["red=lots"])).unwrap_err();
+        assert_eq!(error, "red should be a whole number, got lots");
+
+        let config = Config::parse("[day02]\ngreen = 1").unwrap();
+        let error = Params::new(CUBES, &config.day(2), &[]).unwrap_err();
+        assert!(error.starts_with("no parameter green, there are:\n  red=12  red cubes"));
+
+        assert!(parse_overrides(&["red".to_string()]).is_err());
+        assert!(Config::parse("[day02]\nred = 1.5").is_err());
+        assert!(Config::parse("[two]\nred = 1").is_err());
+        assert_eq!(Config::parse("").unwrap().day(2), BTreeMap::new());
+    }
+}
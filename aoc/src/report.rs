@@ -0,0 +1,307 @@
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use parsers::ParseError;
+use progress::Progress;
+use serde::{Deserialize, Serialize};
+use tracing::info_span;
+
+use crate::answers::{Answers, Status};
+use crate::budget;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    // one JSON object per line, written as soon as each part is solved
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+    // the input could not be parsed, so there is no answer to check
+    Error { error: String },
+    // the part ran out of its time budget and was asked to stop
+    Timeout,
+    // the solution panicked, with what it said
+    Panic { error: String },
+}
+
+// how solving one part went
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    // the parse step on its own, for the days that have one
+    pub parse_ns: Option<u64>,
+    // the whole part, which parses the input again: parse_ns is part of it,
+    // it is not to be added on top
+    pub solve_ns: u64,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+impl Record {
+    // `solver` is the part with its input and parameters; without answers,
+    // like when the puzzle was changed, the answer is not checked. Its
+    // progress is only drawn when it is the only part running
+    pub fn solve(
+        day: u8,
+        part: u8,
+        solver: impl FnOnce(&Progress) -> Result<String, ParseError> + Send + 'static,
+        parse_ns: Option<u64>,
+        answers: Option<&Answers>,
+        timeout: Option<Duration>,
+        draw: bool,
+    ) -> Record {
+        let label = format!("day{:02} part {}", day, part);
+        let label = draw.then_some(label.as_str());
+        let start = Instant::now();
+        let result = info_span!("part", part).in_scope(|| budget::run(label, solver, timeout));
+        let solve_ns = start.elapsed().as_nanos() as u64;
+
+        let (answer, verdict) = match result {
+            None => (None, Verdict::Timeout),
+            Some(Ok(answer)) => {
+                let status = answers.map_or(Status::Unknown, |a| a.check(day, part, &answer));
+                let verdict = match status {
+                    Status::Correct => Verdict::Correct,
+                    Status::Wrong { expected } => Verdict::Wrong { expected },
+                    Status::Unknown => Verdict::Unknown,
+                };
+                (Some(answer), verdict)
+            }
+            Some(Err(error)) => (
+                None,
+                Verdict::Error {
+                    error: error.to_string(),
+                },
+            ),
+        };
+
+        return Record {
+            day,
+            part,
+            answer,
+            parse_ns,
+            solve_ns,
+            verdict,
+        };
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        return match format {
+            Format::Text => self.text(),
+            Format::Json => serde_json::to_string(self).unwrap(),
+        };
+    }
+
+    fn text(&self) -> String {
+        let prefix = format!("day{:02} part {}", self.day, self.part);
+        let answer = self.answer.clone().unwrap_or_default();
+
+        return match &self.verdict {
+            Verdict::Correct => format!("{}: {} (correct)", prefix, answer),
+            Verdict::Wrong { expected } => {
+                format!("{}: {} (WRONG, expected {})", prefix, answer, expected)
+            }
+            Verdict::Unknown => format!("{}: {} (unknown)", prefix, answer),
+            Verdict::Error { error } => format!("{}: cannot parse the input\n{}", prefix, error),
+            Verdict::Timeout => format!(
+                "{}: timed out after {:.1}s",
+                prefix,
+                Duration::from_nanos(self.solve_ns).as_secs_f64()
+            ),
+            Verdict::Panic { error } => format!("{}: panicked: {}", prefix, error),
+        };
+    }
+}
+
+// cells padded to line up under the header, two spaces apart
+pub fn columns<R: AsRef<[String]>>(header: &[&str], rows: &[R]) -> String {
+    let widths = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row.as_ref()[i].chars().count())
+                .chain([header[i].chars().count()])
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let line = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        line(&header.iter().map(|h| h.to_string()).collect::<Vec<_>>()),
+        line(&widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>()),
+    ];
+    lines.extend(rows.iter().map(|row| line(row.as_ref())));
+
+    return lines.join("\n");
+}
+
+pub fn time(f: impl FnOnce()) -> u64 {
+    let start = Instant::now();
+    f();
+    return start.elapsed().as_nanos() as u64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Answers {
+        return Answers::parse("[day06]\npart1 = 288\npart2 = 1").unwrap();
+    }
+
+    #[test]
+    fn test_solve() {
+        let record = Record::solve(
+            6,
+            1,
+            |_| Ok("288".to_string()),
+            Some(5),
+            Some(&answers()),
+            None,
+            false,
+        );
+        assert_eq!(record.answer, Some("288".to_string()));
+        assert_eq!(record.verdict, Verdict::Correct);
+        assert_eq!(record.parse_ns, Some(5));
+
+        let record = Record::solve(
+            6,
+            2,
+            |_| Ok("2".to_string()),
+            None,
+            Some(&answers()),
+            None,
+            false,
+        );
+        assert_eq!(
+            record.verdict,
+            Verdict::Wrong {
+                expected: "1".to_string()
+            }
+        );
+
+        // a changed puzzle has no known answers
+        let record = Record::solve(6, 2, |_| Ok("2".to_string()), None, None, None, false);
+        assert_eq!(record.verdict, Verdict::Unknown);
+
+        let record = Record::solve(
+            6,
+            1,
+            |_| Err(ParseError::new(6, "x", "x", "a number")),
+            None,
+            Some(&answers()),
+            None,
+            false,
+        );
+        assert_eq!(record.answer, None);
+        assert!(matches!(record.verdict, Verdict::Error { .. }));
+    }
+
+    #[test]
+    fn test_solve_timed_out() {
+        let record = Record::solve(
+            5,
+            2,
+            |progress| {
+                while !progress.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Ok("46".to_string())
+            },
+            None,
+            Some(&answers()),
+            Some(Duration::from_millis(100)),
+            false,
+        );
+        assert_eq!(record.answer, None);
+        assert_eq!(record.verdict, Verdict::Timeout);
+        assert!(record.solve_ns >= 100_000_000);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let record = Record {
+            day: 5,
+            part: 2,
+            answer: Some("46".to_string()),
+            parse_ns: None,
+            solve_ns: 10,
+            verdict: Verdict::Wrong {
+                expected: "47".to_string(),
+            },
+        };
+        assert_eq!(
+            record.render(Format::Text),
+            "day05 part 2: 46 (WRONG, expected 47)"
+        );
+    }
+
+    #[test]
+    fn test_render_timeout() {
+        let record = Record {
+            day: 5,
+            part: 2,
+            answer: None,
+            parse_ns: None,
+            solve_ns: 2_050_000_000,
+            verdict: Verdict::Timeout,
+        };
+        assert_eq!(
+            record.render(Format::Text),
+            "day05 part 2: timed out after 2.0s"
+        );
+        assert!(record.render(Format::Json).ends_with(
+            r#""answer":null,"parse_ns":null,"solve_ns":2050000000,"status":"timeout"}"#
+        ));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let record = Record {
+            day: 5,
+            part: 1,
+            answer: Some("35".to_string()),
+            parse_ns: Some(1200),
+            solve_ns: 3400,
+            verdict: Verdict::Correct,
+        };
+        assert_eq!(
+            record.render(Format::Json),
+            r#"{"day":5,"part":1,"answer":"35","parse_ns":1200,"solve_ns":3400,"status":"correct"}"#
+        );
+
+        let record = Record {
+            day: 4,
+            part: 1,
+            answer: None,
+            parse_ns: None,
+            solve_ns: 1,
+            verdict: Verdict::Error {
+                error: "expected '|'".to_string(),
+            },
+        };
+        let json = record.render(Format::Json);
+        assert!(!json.contains('\n'));
+        assert!(json.ends_with(
+            r#""answer":null,"parse_ns":null,"solve_ns":1,"status":"error","error":"expected '|'"}"#
+        ));
+    }
+}
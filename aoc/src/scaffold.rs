@@ -0,0 +1,362 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days;
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+const REGISTRY_TEMPLATE: &str = include_str!("../templates/registry.rs.tmpl");
+const YEAR_TEMPLATE: &str = include_str!("../templates/year.rs.tmpl");
+
+const REGISTRY_MARKER: &str = "        // `aoc new` registers the days it creates above this line";
+const YEAR_MARKER: &str = "        // `aoc new` registers the years it creates above this line";
+const YEAR_MOD_MARKER: &str = "// `aoc new` declares the years it creates above this line";
+
+fn render(template: &str, year: u16, number: u8) -> String {
+    return template
+        .replace("{{package}}", &format!("y{}-day{:02}", year, number))
+        .replace("{{crate}}", &format!("y{}_day{:02}", year, number))
+        .replace("{{year}}", &year.to_string())
+        .replace("{{number}}", &number.to_string());
+}
+
+// creates YYYY/dayNN with its crate files, adds it to the workspace and
+// registers it with the runner, along with the year if it is the first day
+// of it; returns the files that were written
+pub fn create(root: &Path, year: u16, number: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&number) {
+        return Err(format!("there is no day {}, days go from 1 to 25", number));
+    }
+    if year < 2015 {
+        return Err(format!(
+            "there is no {}, Advent of Code started in 2015",
+            year
+        ));
+    }
+
+    let dir = days::dir(root, year, number);
+    if dir.exists() {
+        return Err(format!(
+            "{} already exists, not overwriting it",
+            dir.display()
+        ));
+    }
+
+    // check everything that has to be edited before writing anything
+    let workspace_path = root.join("Cargo.toml");
+    let runner_path = root.join("aoc").join("Cargo.toml");
+    let days_path = root.join("aoc").join("src").join("days.rs");
+    let registry_path = root
+        .join("aoc")
+        .join("src")
+        .join("days")
+        .join(format!("y{}.rs", year));
+    let member = format!("{}/day{:02}", year, number);
+    let workspace = add_member(&read(&workspace_path)?, &member)?;
+    let runner = add_dependency(&read(&runner_path)?, year, number)?;
+    let mut files = Vec::new();
+    let registry = if registry_path.exists() {
+        read(&registry_path)?
+    } else {
+        files.push((days_path.clone(), add_year(&read(&days_path)?, year)?));
+        render(YEAR_TEMPLATE, year, number)
+    };
+    let registry = add_registry_entry(&registry, year, number)?;
+
+    files.extend([
+        (dir.join("Cargo.toml"), render(CARGO_TEMPLATE, year, number)),
+        (
+            dir.join("src").join("lib.rs"),
+            render(LIB_TEMPLATE, year, number),
+        ),
+        (
+            dir.join("src").join("main.rs"),
+            render(MAIN_TEMPLATE, year, number),
+        ),
+        // empty until `aoc encrypt` replaces it with the encrypted input
+        (dir.join("src").join("input.enc"), String::new()),
+        // placeholders until `aoc extract` saves the examples
+        (dir.join("fixtures").join("example1.txt"), String::new()),
+        (
+            dir.join("fixtures").join("example1.answer"),
+            "0\n".to_string(),
+        ),
+        (workspace_path, workspace),
+        (runner_path, runner),
+        (registry_path, registry),
+    ]);
+
+    fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    for (path, content) in files.iter() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+        }
+        fs::write(path, content).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+
+    return Ok(files.into_iter().map(|(path, _)| path).collect());
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    return fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e));
+}
+
+// adds the crate to the `members = [...]` list, keeping it sorted and one
+// member per line
+fn add_member(manifest: &str, name: &str) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .ok_or("no workspace members list in Cargo.toml")?
+        + "members = [".len();
+    let end = start
+        + manifest[start..]
+            .find(']')
+            .ok_or("unterminated workspace members list in Cargo.toml")?;
+
+    let mut members = manifest[start..end]
+        .split(',')
+        .map(|m| m.trim().trim_matches('"'))
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    if members.contains(&name) {
+        return Err(format!("{} is already a workspace member", name));
+    }
+    members.push(name);
+    members.sort();
+
+    let list = members
+        .iter()
+        .map(|m| format!("\n    \"{}\",", m))
+        .collect::<String>()
+        + "\n";
+    return Ok(format!(
+        "{}{}{}",
+        &manifest[..start],
+        list,
+        &manifest[end..]
+    ));
+}
+
+// adds a path dependency on the new day right after the last one
+fn add_dependency(manifest: &str, year: u16, number: u8) -> Result<String, String> {
+    let package = format!("y{}-day{:02}", year, number);
+    let line = format!(
+        "{} = {{ path = \"../{}/day{:02}\" }}",
+        package, year, number
+    );
+    let mut lines = manifest.lines().collect::<Vec<_>>();
+
+    let last_day = lines
+        .iter()
+        .rposition(|l| l.starts_with('y') && l.contains("-day") && l.contains("path = "))
+        .ok_or("no day dependencies in aoc/Cargo.toml")?;
+    if lines
+        .iter()
+        .any(|l| l.starts_with(&format!("{} =", package)))
+    {
+        return Err(format!("aoc already depends on {}", package));
+    }
+    lines.insert(last_day + 1, &line);
+
+    return Ok(lines.join("\n") + "\n");
+}
+
+fn add_registry_entry(registry: &str, year: u16, number: u8) -> Result<String, String> {
+    if !registry.contains(REGISTRY_MARKER) {
+        return Err(format!(
+            "cannot find where to register the day in aoc/src/days/y{}.rs",
+            year
+        ));
+    }
+
+    return Ok(registry.replacen(
+        REGISTRY_MARKER,
+        &format!(
+            "{}{}",
+            render(REGISTRY_TEMPLATE, year, number),
+            REGISTRY_MARKER
+        ),
+        1,
+    ));
+}
+
+// declares the module of a new year in aoc/src/days.rs and adds its days to
+// the registry
+fn add_year(days: &str, year: u16) -> Result<String, String> {
+    if !days.contains(YEAR_MOD_MARKER) || !days.contains(YEAR_MARKER) {
+        return Err("cannot find where to register the year in aoc/src/days.rs".to_string());
+    }
+
+    return Ok(days
+        .replacen(
+            YEAR_MOD_MARKER,
+            &format!("mod y{};\n{}", year, YEAR_MOD_MARKER),
+            1,
+        )
+        .replacen(
+            YEAR_MARKER,
+            &format!("        y{}::days(),\n{}", year, YEAR_MARKER),
+            1,
+        ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = "[workspace]
+resolver = \"2\"
+members = [
+    \"2023/day01\",
+    \"2023/day02\",
+    \"aoc\",
+]
+";
+
+    const RUNNER: &str = "[dependencies]
+clap = \"4\"
+y2023-day01 = { path = \"../2023/day01\" }
+y2023-day02 = { path = \"../2023/day02\" }
+
+[lints]
+workspace = true
+";
+
+    fn fake_root() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let src = root.path().join("aoc").join("src");
+        fs::create_dir_all(src.join("days")).unwrap();
+        fs::create_dir_all(root.path().join("2023").join("day01")).unwrap();
+        fs::write(root.path().join("Cargo.toml"), WORKSPACE).unwrap();
+        fs::write(root.path().join("aoc").join("Cargo.toml"), RUNNER).unwrap();
+        fs::write(
+            src.join("days.rs"),
+            format!(
+                "mod y2023;\n{}\n    [\n        y2023::days(),\n{}\n    ]\n",
+                YEAR_MOD_MARKER, YEAR_MARKER
+            ),
+        )
+        .unwrap();
+        fs::write(
+            src.join("days").join("y2023.rs"),
+            format!("    return vec![\n{}\n    ];\n", REGISTRY_MARKER),
+        )
+        .unwrap();
+        return root;
+    }
+
+    #[test]
+    fn test_add_member() {
+        let output = add_member(WORKSPACE, "2023/day07").unwrap();
+        assert!(output.contains(
+            "members = [\n    \"2023/day01\",\n    \"2023/day02\",\n    \"2023/day07\",\n    \"aoc\",\n]\n"
+        ));
+        assert!(add_member(WORKSPACE, "2023/day02").is_err());
+        assert!(add_member("[workspace]\n", "2023/day07").is_err());
+        // a one line list is spread over lines too
+        assert_eq!(
+            add_member("members = [\"aoc\"]", "2024/day01").unwrap(),
+            "members = [\n    \"2024/day01\",\n    \"aoc\",\n]"
+        );
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let output = add_dependency(RUNNER, 2023, 7).unwrap();
+        assert!(output.contains(
+            "y2023-day02 = { path = \"../2023/day02\" }\ny2023-day07 = { path = \"../2023/day07\" }\n\n[lints]"
+        ));
+        assert!(add_dependency(RUNNER, 2023, 1).is_err());
+        // the same day of another year is another crate
+        assert!(add_dependency(RUNNER, 2024, 1)
+            .unwrap()
+            .contains("y2024-day01 = { path = \"../2024/day01\" }"));
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let registry = format!("vec![\n{}\n]", REGISTRY_MARKER);
+        let output = add_registry_entry(&registry, 2023, 7).unwrap();
+        assert!(output.contains("year: 2023,"));
+        assert!(output.contains("number: 7,"));
+        assert!(output.contains("y2023_day07::part1(input)"));
+        assert!(output.find("number: 7").unwrap() < output.find(REGISTRY_MARKER).unwrap());
+        assert!(add_registry_entry("vec![]", 2023, 7).is_err());
+    }
+
+    #[test]
+    fn test_add_year() {
+        let days = format!("{}\n[\n{}\n]", YEAR_MOD_MARKER, YEAR_MARKER);
+        let output = add_year(&days, 2024).unwrap();
+        assert!(output.starts_with("mod y2024;\n"));
+        assert!(output.contains("        y2024::days(),\n"));
+        assert!(add_year("[]", 2024).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let root = fake_root();
+        let files = create(root.path(), 2023, 7).unwrap();
+        assert_eq!(files.len(), 9);
+
+        let day = root.path().join("2023").join("day07");
+        let manifest = fs::read_to_string(day.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"y2023-day07\""));
+        assert!(manifest.contains("path = \"../../parsers\""));
+        let main = fs::read_to_string(day.join("src").join("main.rs")).unwrap();
+        assert!(main.contains("\nuse y2023_day07::{part1, part2};\n"));
+        let lib = fs::read_to_string(day.join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("fixtures::cases! {"));
+        assert_eq!(
+            fs::read_to_string(day.join("src").join("input.enc")).unwrap(),
+            ""
+        );
+
+        let workspace = fs::read_to_string(root.path().join("Cargo.toml")).unwrap();
+        assert!(workspace.contains("\"2023/day07\""));
+        let registry = fs::read_to_string(root.path().join("aoc/src/days/y2023.rs")).unwrap();
+        assert!(registry.contains("number: 7,"));
+    }
+
+    #[test]
+    fn test_create_new_year() {
+        let root = fake_root();
+        let files = create(root.path(), 2024, 1).unwrap();
+        // the registry of the year, and the runner's list of years
+        assert_eq!(files.len(), 10);
+
+        let registry = fs::read_to_string(root.path().join("aoc/src/days/y2024.rs")).unwrap();
+        assert!(registry.starts_with("// the days of 2024, the crates in 2024/"));
+        assert!(registry.contains("year: 2024,"));
+        assert!(registry.contains(REGISTRY_MARKER));
+        let days = fs::read_to_string(root.path().join("aoc/src/days.rs")).unwrap();
+        assert!(days.contains("mod y2024;\n"));
+        assert!(days.contains("y2024::days(),"));
+        assert!(root.path().join("2024/day01/src/main.rs").exists());
+
+        // the next day of the year goes into the same registry
+        create(root.path(), 2024, 2).unwrap();
+        let days_again = fs::read_to_string(root.path().join("aoc/src/days.rs")).unwrap();
+        assert_eq!(days_again, days);
+    }
+
+    #[test]
+    fn test_create_refuses_to_overwrite() {
+        let root = fake_root();
+        assert!(create(root.path(), 2023, 1).is_err());
+        // nothing was touched
+        let workspace = fs::read_to_string(root.path().join("Cargo.toml")).unwrap();
+        assert_eq!(workspace, WORKSPACE);
+    }
+
+    #[test]
+    fn test_create_bad_day() {
+        let root = fake_root();
+        assert!(create(root.path(), 2023, 0).is_err());
+        assert!(create(root.path(), 2023, 26).is_err());
+        assert!(create(root.path(), 1999, 1).is_err());
+    }
+}
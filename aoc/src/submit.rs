@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    // wrong, without a hint about which side
+    Wrong,
+    // submitted during a cooldown, the answer was not checked
+    Wait,
+    // the part was already solved, the answer was not checked
+    AlreadySolved,
+    Unrecognized,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub outcome: Outcome,
+    // seconds to wait before the next submission, when the page says so
+    pub wait: Option<u64>,
+}
+
+pub struct Client {
+    pub endpoint: String,
+    pub session: String,
+    pub year: u16,
+}
+
+impl Client {
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Response, String> {
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.endpoint.trim_end_matches('/'),
+            self.year,
+            day
+        );
+        let body = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| format!("cannot submit to {}: {}", url, e))?
+            .into_string()
+            .map_err(|e| format!("cannot read response from {}: {}", url, e))?;
+
+        return Ok(parse_response(&body));
+    }
+}
+
+pub fn parse_response(page: &str) -> Response {
+    // only the <article> holds the message, the rest is the site chrome
+    let text = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else {
+        Outcome::Unrecognized
+    };
+
+    return Response {
+        outcome,
+        wait: parse_wait(text),
+    };
+}
+
+// the cooldown comes as either "You have 4m 32s left to wait"
+// or "please wait 5 minutes before trying again"
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        return rest[..end]
+            .split_whitespace()
+            .map(|token| {
+                let (number, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    if let Some(start) = text.find("please wait ") {
+        let rest = &text[start + "please wait ".len()..];
+        let mut words = rest.split_whitespace();
+        let count = match words.next()? {
+            "one" => 1,
+            n => n.parse::<u64>().ok()?,
+        };
+        return match words.next()? {
+            "second" | "seconds" => Some(count),
+            "minute" | "minutes" => Some(count * 60),
+            _ => None,
+        };
+    }
+
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn page(message: &str) -> String {
+        return format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        );
+    }
+
+    #[test]
+    fn test_parse_correct() {
+        let response = parse_response(&page(
+            "That's the right answer! You are one gold star closer to restoring snow operations.",
+        ));
+        assert_eq!(response.outcome, Outcome::Correct);
+        assert_eq!(response.wait, None);
+    }
+
+    #[test]
+    fn test_parse_too_high_and_too_low() {
+        let high = page("That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; please wait one minute before trying again.");
+        let low = page("That's not the right answer; your answer is too low. If you're stuck, make sure you're using the full input data; please wait 5 minutes before trying again.");
+        assert_eq!(
+            parse_response(&high),
+            Response {
+                outcome: Outcome::TooHigh,
+                wait: Some(60)
+            }
+        );
+        assert_eq!(
+            parse_response(&low),
+            Response {
+                outcome: Outcome::TooLow,
+                wait: Some(300)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_wrong() {
+        let response = parse_response(&page("That's not the right answer. If you're stuck, make sure you're using the full input data."));
+        assert_eq!(response.outcome, Outcome::Wrong);
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        let response = parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait. [Return to Day 5]"));
+        assert_eq!(
+            response,
+            Response {
+                outcome: Outcome::Wait,
+                wait: Some(272)
+            }
+        );
+        assert_eq!(parse_wait("You have 9s left to wait."), Some(9));
+    }
+
+    #[test]
+    fn test_parse_already_solved() {
+        let response = parse_response(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?",
+        ));
+        assert_eq!(response.outcome, Outcome::AlreadySolved);
+    }
+
+    #[test]
+    fn test_parse_unrecognized() {
+        assert_eq!(
+            parse_response("<html>Puzzle inputs differ by user.</html>").outcome,
+            Outcome::Unrecognized
+        );
+    }
+
+    #[test]
+    fn test_submit_to_mock_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse::<usize>().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            let body = page("That's not the right answer; your answer is too low.");
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            return request;
+        });
+
+        let client = Client {
+            endpoint,
+            session: "secret".to_string(),
+            year: 2023,
+        };
+        let response = client.submit(5, 2, "46").unwrap();
+        assert_eq!(response.outcome, Outcome::TooLow);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer "));
+        assert!(request.contains("session=secret"));
+        assert!(request.ends_with("level=2&answer=46"));
+    }
+}
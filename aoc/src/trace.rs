@@ -0,0 +1,70 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::{filter::LevelFilter, fmt, prelude::*, EnvFilter, Layer};
+
+// overrides -v when set, in the syntax of `EnvFilter`, like AOC_LOG=y2023_day05=trace
+pub const ENV: &str = "AOC_LOG";
+
+// warnings only by default, each -v shows one more level
+pub fn level(verbose: u8) -> LevelFilter {
+    return match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+}
+
+// logs go to stderr so they never mix with the answers; the flamegraph, if
+// any, is written when the returned guard is dropped
+pub fn init(
+    verbose: u8,
+    flamegraph: Option<&Path>,
+) -> Result<Option<FlushGuard<BufWriter<File>>>, String> {
+    let filter = EnvFilter::builder()
+        .with_default_directive(level(verbose).into())
+        .with_env_var(ENV)
+        .from_env()
+        .map_err(|e| format!("invalid {}: {}", ENV, e))?;
+    let stderr = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(filter);
+
+    // the spans of days, parts and parse steps, whatever the verbosity
+    let (flame, guard) = match flamegraph {
+        Some(path) => {
+            let (layer, guard) = FlameLayer::with_file(path)
+                .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+            let layer = layer
+                .with_threads_collapsed(true)
+                .with_filter(LevelFilter::DEBUG);
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(flame)
+        .try_init()
+        .map_err(|e| e.to_string())?;
+
+    return Ok(guard);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), LevelFilter::WARN);
+        assert_eq!(level(1), LevelFilter::INFO);
+        assert_eq!(level(2), LevelFilter::DEBUG);
+        assert_eq!(level(3), LevelFilter::TRACE);
+        assert_eq!(level(9), LevelFilter::TRACE);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+use crate::days::{self, Day};
+use crate::report::{Format, Record};
+
+// saves in an editor come as a burst of events, wait for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+// where a change triggers a new run: the day's crate, and the parsers every
+// day is built on
+pub fn paths(root: &Path, year: u16, day: u8) -> Vec<PathBuf> {
+    let crate_dir = days::dir(root, year, day);
+
+    return [
+        crate_dir.join("src"),
+        crate_dir.join("fixtures"),
+        crate_dir.join("Cargo.toml"),
+        root.join("parsers").join("src"),
+    ]
+    .into_iter()
+    .filter(|path| path.exists())
+    .collect();
+}
+
+pub fn watch(root: &Path, answers: &Path, day: &Day) -> Result<(), String> {
+    let paths = paths(root, day.year, day.number);
+    if paths.is_empty() {
+        return Err(format!(
+            "nothing to watch for {} of {}",
+            day.name(),
+            day.year
+        ));
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE, tx).map_err(|e| e.to_string())?;
+    for path in &paths {
+        debouncer
+            .watcher()
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|e| format!("cannot watch {}: {}", path.display(), e))?;
+        eprintln!("watching {}", path.display());
+    }
+
+    let mut previous = BTreeMap::new();
+    run_once(root, answers, day, &mut previous);
+    while let Ok(first) = rx.recv() {
+        // one save can still arrive in a few batches, take them all before
+        // starting over
+        let mut changed = BTreeSet::new();
+        let mut next = Some(first);
+        while let Some(events) = next {
+            match events {
+                Ok(events) => changed.extend(events.into_iter().map(|event| event.path)),
+                Err(e) => eprintln!("watch error: {}", e),
+            }
+            next = rx.recv_timeout(DEBOUNCE).ok();
+        }
+        for path in changed {
+            eprintln!("changed {}", path.display());
+        }
+        run_once(root, answers, day, &mut previous);
+    }
+
+    return Ok(());
+}
+
+// rebuild, test and solve, then show how the answers moved since the last run
+fn run_once(root: &Path, answers: &Path, day: &Day, previous: &mut BTreeMap<u8, String>) {
+    let name = day.name();
+    eprintln!("\n== {} {} ==", day.year, name);
+
+    let build = cargo(root)
+        .args(["build", "--release", "--quiet", "-p", "aoc"])
+        .status();
+    if !build.is_ok_and(|status| status.success()) {
+        eprintln!("build failed, waiting for the next change");
+        return;
+    }
+
+    let tests = cargo(root)
+        .args(["test", "--quiet", "-p", &day.package()])
+        .stdout(Stdio::null())
+        .status();
+    match tests {
+        Ok(status) if status.success() => eprintln!("tests passed"),
+        _ => eprintln!("tests FAILED"),
+    }
+
+    // the answers come from the binary just built, the runner itself still
+    // has the old code of the day in it
+    let mut command = cargo(root);
+    command.args(["run", "--release", "--quiet", "-p", "aoc", "--"]);
+    command.arg("--root").arg(root);
+    command.arg("--answers").arg(answers);
+    command.args(["--year", &day.year.to_string()]);
+    command.args(["run", "--day", &day.number.to_string(), "--format", "json"]);
+    let output = match command.stderr(Stdio::inherit()).output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("cannot run {}: {}", name, e);
+            return;
+        }
+    };
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match serde_json::from_str::<Record>(line) {
+            Ok(record) => println!("{}", diff(previous, &record)),
+            // anything a solution prints on its own
+            Err(_) => println!("{}", line),
+        }
+    }
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(std::env::var("CARGO").unwrap_or("cargo".to_string()));
+    command.current_dir(root);
+    return command;
+}
+
+// the record as `run` shows it, with the answer of the previous run if it changed
+pub fn diff(previous: &mut BTreeMap<u8, String>, record: &Record) -> String {
+    let text = record.render(Format::Text);
+    let Some(answer) = &record.answer else {
+        return text;
+    };
+
+    let change = match previous.insert(record.part, answer.clone()) {
+        Some(before) if before != *answer => format!(", was {}", before),
+        _ => String::new(),
+    };
+
+    return text + &change;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Verdict;
+
+    fn record(part: u8, answer: &str) -> Record {
+        return Record {
+            day: 5,
+            part,
+            answer: Some(answer.to_string()),
+            parse_ns: None,
+            solve_ns: 1,
+            verdict: Verdict::Unknown,
+        };
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut previous = BTreeMap::new();
+        assert_eq!(
+            diff(&mut previous, &record(1, "35")),
+            "day05 part 1: 35 (unknown)"
+        );
+        assert_eq!(
+            diff(&mut previous, &record(1, "35")),
+            "day05 part 1: 35 (unknown)"
+        );
+        assert_eq!(
+            diff(&mut previous, &record(1, "36")),
+            "day05 part 1: 36 (unknown), was 35"
+        );
+        assert_eq!(
+            diff(&mut previous, &record(2, "46")),
+            "day05 part 2: 46 (unknown)"
+        );
+    }
+
+    #[test]
+    fn test_diff_keeps_answer_over_error() {
+        let mut previous = BTreeMap::new();
+        diff(&mut previous, &record(1, "35"));
+        let error = Record {
+            answer: None,
+            verdict: Verdict::Error {
+                error: "bad".to_string(),
+            },
+            ..record(1, "")
+        };
+        assert!(diff(&mut previous, &error).contains("cannot parse the input"));
+        // once the input parses again, it is compared with the last answer
+        assert!(diff(&mut previous, &record(1, "36")).ends_with(", was 35"));
+    }
+
+    #[test]
+    fn test_paths() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("2024/day07/src")).unwrap();
+        std::fs::write(root.path().join("2024/day07/Cargo.toml"), "").unwrap();
+        assert_eq!(
+            paths(root.path(), 2024, 7),
+            [
+                root.path().join("2024/day07/src"),
+                root.path().join("2024/day07/Cargo.toml")
+            ]
+        );
+        assert!(paths(root.path(), 2024, 8).is_empty());
+        assert!(paths(root.path(), 2023, 7).is_empty());
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let record = record(2, "46");
+        let json = record.render(Format::Json);
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+    }
+}
@@ -0,0 +1,221 @@
+// the tests every day has: the examples of the puzzle description against
+// their answers, and the real input against the answer in answers.toml.
+//
+// a day lists its parts, each with the fixtures it is checked on, and gets
+// one test per fixture named after it, in a module named after the part:
+//
+//     fixtures::cases! {
+//         part1 => example1, input;
+//         part2 with |input| part2(input, 7) => example2;
+//     }
+//
+// `example1` reads fixtures/example1.txt and expects fixtures/example1.answer,
+// as `aoc extract` writes them; `input` is the real input, which is skipped
+// when it is missing, cannot be decrypted for lack of a key, or has no answer
+// yet. A part is solved by the function named after it unless `with` gives
+// another one; its answer is compared as a string.
+
+use std::fs;
+use std::path::Path;
+
+use inputs::{InputError, Key};
+
+mod repeat;
+
+pub use repeat::repeat;
+
+#[macro_export]
+macro_rules! cases {
+    (@solver $part:ident) => {
+        $part
+    };
+    (@solver $part:ident, $solver:expr) => {
+        $solver
+    };
+
+    (@case $part:ident, $solver:expr; input $(, $($rest:ident),+)?) => {
+        #[test]
+        fn input() {
+            if let Some((input, answer)) =
+                $crate::real(env!("CARGO_MANIFEST_DIR"), stringify!($part))
+            {
+                assert_eq!(($solver)(&input).unwrap().to_string(), answer);
+            }
+        }
+        $($crate::cases!(@case $part, $solver; $($rest),+);)?
+    };
+    (@case $part:ident, $solver:expr; $example:ident $(, $($rest:ident),+)?) => {
+        #[test]
+        fn $example() {
+            let input = include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/fixtures/",
+                stringify!($example),
+                ".txt"
+            ));
+            let answer = include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/fixtures/",
+                stringify!($example),
+                ".answer"
+            ));
+            assert_eq!(($solver)(input).unwrap().to_string(), answer.trim());
+        }
+        $($crate::cases!(@case $part, $solver; $($rest),+);)?
+    };
+
+    ($($part:ident $(with $solver:expr)? => $($case:ident),+);+ $(;)?) => {
+        $(
+            mod $part {
+                use super::*;
+
+                $crate::cases!(
+                    @case $part, ($crate::cases!(@solver $part $(, $solver)?)); $($case),+
+                );
+            }
+        )+
+    };
+}
+
+// why there is nothing to check the real input with
+#[derive(Debug, PartialEq)]
+enum Skip {
+    // not there yet, which is fine
+    Missing(String),
+    // there, but unusable, which is a failure
+    Broken(String),
+}
+
+// the real input of the day whose crate is in `crate_dir`, and the known
+// answer of `part`; None, after saying why, when either is missing
+pub fn real(crate_dir: &str, part: &str) -> Option<(String, String)> {
+    return match find(Path::new(crate_dir), part, || Key::load(inputs::root())) {
+        Ok(found) => Some(found),
+        Err(Skip::Missing(reason)) => {
+            eprintln!("skipping the real input of {}: {}", part, reason);
+            None
+        }
+        Err(Skip::Broken(reason)) => panic!("{}", reason),
+    };
+}
+
+fn find(
+    crate_dir: &Path,
+    part: &str,
+    key: impl FnOnce() -> Result<Key, InputError>,
+) -> Result<(String, String), Skip> {
+    let path = crate_dir.join("src").join("input.enc");
+    let bytes = fs::read(&path).unwrap_or_default();
+    if bytes.is_empty() {
+        return Err(Skip::Missing(format!("no input in {}", path.display())));
+    }
+    let input = inputs::open_with(&bytes, key).map_err(|e| match e {
+        InputError::NoKey { .. } => Skip::Missing(e.to_string()),
+        _ => Skip::Broken(format!("{}: {}", path.display(), e)),
+    })?;
+
+    // the crates of a year are next to its answers, in directories named
+    // after their day like the tables of the answers
+    let day = crate_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let path = crate_dir.parent().unwrap_or(crate_dir).join("answers.toml");
+    let missing = || {
+        return Skip::Missing(format!(
+            "no answer for {} {} in {}",
+            day,
+            part,
+            path.display()
+        ));
+    };
+    let answers = match fs::read_to_string(&path) {
+        Ok(text) => text
+            .parse::<toml::Table>()
+            .map_err(|e| Skip::Broken(format!("{}: {}", path.display(), e)))?,
+        Err(_) => return Err(missing()),
+    };
+    let answer = match answers.get(&day).and_then(|table| table.get(part)) {
+        Some(toml::Value::Integer(answer)) => answer.to_string(),
+        Some(toml::Value::String(answer)) => answer.clone(),
+        Some(_) => {
+            return Err(Skip::Broken(format!(
+                "{}: {}.{} should be a string or an integer",
+                path.display(),
+                day,
+                part
+            )))
+        }
+        None => return Err(missing()),
+    };
+
+    return Ok((input, answer));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> Result<Key, InputError> {
+        return Ok(Key::new("hunter2"));
+    }
+
+    // a year with one day, that has an input and the answer of part 1
+    fn fake_year() -> tempfile::TempDir {
+        let year = tempfile::tempdir().unwrap();
+        let src = year.path().join("day07").join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("input.enc"), key().unwrap().encrypt("32T3K 765\n")).unwrap();
+        fs::write(year.path().join("answers.toml"), "[day07]\npart1 = 6440\n").unwrap();
+        return year;
+    }
+
+    #[test]
+    fn test_find() {
+        let year = fake_year();
+        let day = year.path().join("day07");
+        assert_eq!(
+            find(&day, "part1", key),
+            Ok(("32T3K 765\n".to_string(), "6440".to_string()))
+        );
+        assert!(matches!(find(&day, "part2", key), Err(Skip::Missing(_))));
+    }
+
+    #[test]
+    fn test_skipped() {
+        let year = fake_year();
+        let day = year.path().join("day07");
+        let no_key = || {
+            return Err(InputError::NoKey {
+                keyfile: year.path().join(".input-key"),
+            });
+        };
+        match find(&day, "part1", no_key) {
+            Err(Skip::Missing(reason)) => assert!(reason.contains("there is no key")),
+            other => panic!("expected the test to be skipped, got {:?}", other),
+        }
+
+        // a day that has no input yet, like one `aoc new` just made
+        fs::write(day.join("src").join("input.enc"), "").unwrap();
+        assert!(matches!(find(&day, "part1", key), Err(Skip::Missing(_))));
+        assert!(matches!(
+            find(&year.path().join("day08"), "part1", key),
+            Err(Skip::Missing(_))
+        ));
+    }
+
+    #[test]
+    fn test_broken() {
+        let year = fake_year();
+        let day = year.path().join("day07");
+        // the wrong key fails the test instead of skipping it
+        let wrong = || Ok(Key::new("hunter3"));
+        match find(&day, "part1", wrong) {
+            Err(Skip::Broken(reason)) => assert!(reason.contains("does not decrypt")),
+            other => panic!("expected a failure, got {:?}", other),
+        }
+
+        fs::write(year.path().join("answers.toml"), "[day07]\npart1 = [1]\n").unwrap();
+        assert!(matches!(find(&day, "part1", key), Err(Skip::Broken(_))));
+    }
+}
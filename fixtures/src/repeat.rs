@@ -0,0 +1,52 @@
+// inputs far bigger than what a test could hold, for the reader variants of
+// the parts
+use std::io::{self, BufRead, BufReader, Read};
+
+// `count` copies of `line`, made up as they are read
+struct Repeat {
+    line: &'static [u8],
+    count: u64,
+    offset: usize,
+}
+
+impl Read for Repeat {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() && self.count > 0 {
+            let rest = &self.line[self.offset..];
+            let n = rest.len().min(buf.len() - written);
+            buf[written..written + n].copy_from_slice(&rest[..n]);
+            written += n;
+            self.offset += n;
+            if self.offset == self.line.len() {
+                self.offset = 0;
+                self.count -= 1;
+            }
+        }
+        return Ok(written);
+    }
+}
+
+pub fn repeat(line: &'static str, count: u64) -> impl BufRead {
+    return BufReader::with_capacity(
+        1 << 16,
+        Repeat {
+            line: line.as_bytes(),
+            count,
+            offset: 0,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat() {
+        let mut text = String::new();
+        repeat("ab\n", 3).read_to_string(&mut text).unwrap();
+        assert_eq!(text, "ab\nab\nab\n");
+        assert_eq!(repeat("ab\n", 0).lines().count(), 0);
+    }
+}
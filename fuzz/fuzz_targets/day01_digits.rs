@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for row in input.lines() {
+        let _ = y2023_day01::get_first_and_last_digits_improved(row);
+    }
+    let _ = y2023_day01::part1(input);
+    let _ = y2023_day01::part2(input);
+    let _ = y2023_day01::part1_from_reader(input.as_bytes());
+    let _ = y2023_day01::part2_from_reader(input.as_bytes());
+});
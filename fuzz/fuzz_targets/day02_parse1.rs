@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use y2023_day02::Cubes;
+
+fuzz_target!(|input: &str| {
+    for line in input.lines() {
+        let _ = y2023_day02::parse1(line);
+    }
+    let bag = Cubes {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+    let _ = y2023_day02::part1(input, &bag);
+});
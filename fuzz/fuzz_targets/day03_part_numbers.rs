@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // part_numbers looks at the lines around the one it is given
+    for line_number in 0..input.trim().lines().count() {
+        let _ = y2023_day03::part_numbers(input, line_number);
+    }
+    let _ = y2023_day03::part1(input);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for line in input.lines() {
+        let _ = y2023_day04::get_card(line);
+    }
+    let _ = y2023_day04::part1(input);
+    let _ = y2023_day04::part1_from_reader(input.as_bytes());
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// part 2 looks up every seed, more than this many would time the run out
+const SEEDS: u64 = 1 << 16;
+
+fuzz_target!(|input: &str| {
+    let _ = y2023_day05::parse(input);
+    let _ = y2023_day05::part1(input);
+    if let Ok((ranges, _)) = y2023_day05::parse_ranges(input) {
+        let seeds = ranges
+            .iter()
+            .fold(0u64, |seeds, &(_, len)| seeds.saturating_add(len));
+        if seeds <= SEEDS {
+            let _ = y2023_day05::part2(input);
+        }
+    }
+});
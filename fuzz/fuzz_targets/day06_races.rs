@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// the parts try every way to hold the button, longer races would time the
+// run out
+const TIME: u128 = 1 << 16;
+
+fuzz_target!(|input: &str| {
+    if let Ok(races) = y2023_day06::get_races(input) {
+        if races.iter().all(|&(time, _)| time <= TIME) {
+            let _ = y2023_day06::part1(input);
+        }
+    }
+    if let Ok((time, _)) = y2023_day06::get_races_2(input) {
+        if time <= TIME {
+            let _ = y2023_day06::part2(input);
+        }
+    }
+});
@@ -0,0 +1,279 @@
+// the puzzle inputs are not ours to hand out, so they are committed
+// encrypted and only turned back into text when a solution reads them.
+//
+// the key is any secret text, looked up in this order: the AOC_INPUT_KEY
+// variable, the file AOC_INPUT_KEYFILE points at, and .input-key at the root
+// of the repository. The inputs are sealed with ChaCha20-Poly1305, so the
+// wrong key fails to open them instead of handing garbage to a solution.
+//
+// the commits from before the inputs were encrypted still hold them in plain
+// text, see the README for what removing them from the history takes.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+pub const KEYFILE_VAR: &str = "AOC_INPUT_KEYFILE";
+// gitignored, next to the workspace Cargo.toml
+pub const KEYFILE: &str = ".input-key";
+
+// what an encrypted input starts with, anything else is read as plain text
+const MAGIC: &[u8] = b"aoc-input\0";
+const NONCE_LEN: usize = 12;
+
+// the root of the repository the day crates were built in
+pub fn root() -> &'static Path {
+    return Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+}
+
+#[derive(Clone, PartialEq)]
+pub enum InputError {
+    // none of the places a key can come from had one
+    NoKey { keyfile: PathBuf },
+    // the key is not the one the input was encrypted with, or the file was damaged
+    WrongKey,
+    Unreadable { path: PathBuf, reason: String },
+    NotText,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            InputError::NoKey { keyfile } => write!(
+                f,
+                "the input is encrypted and there is no key: set {}, point {} at a file holding it, or put it in {}",
+                KEY_VAR,
+                KEYFILE_VAR,
+                keyfile.display()
+            ),
+            InputError::WrongKey => write!(
+                f,
+                "the input does not decrypt with this key, it was encrypted with another one or is damaged"
+            ),
+            InputError::Unreadable { path, reason } => {
+                write!(f, "cannot read the key in {}: {}", path.display(), reason)
+            }
+            InputError::NotText => write!(f, "the input is not UTF-8 text"),
+        };
+    }
+}
+
+// shown as is when main returns it
+impl fmt::Debug for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Display::fmt(self, f);
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub struct Key(ChaCha20Poly1305);
+
+impl Key {
+    // any text works as a secret, it is hashed into the 32 bytes of the key;
+    // surrounding whitespace is dropped so a trailing newline in a file
+    // makes no difference
+    pub fn new(secret: &str) -> Key {
+        let hash = Sha256::digest(secret.trim().as_bytes());
+        return Key(ChaCha20Poly1305::new(&hash));
+    }
+
+    // a fresh random secret, as hex
+    pub fn generate() -> String {
+        return ChaCha20Poly1305::generate_key(&mut OsRng)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+    }
+
+    // the key from the environment, or from .input-key in `root`
+    pub fn load(root: &Path) -> Result<Key, InputError> {
+        return Key::find(
+            env::var(KEY_VAR).ok(),
+            env::var_os(KEYFILE_VAR).map(PathBuf::from),
+            &root.join(KEYFILE),
+        );
+    }
+
+    fn find(
+        secret: Option<String>,
+        keyfile: Option<PathBuf>,
+        default: &Path,
+    ) -> Result<Key, InputError> {
+        if let Some(secret) = secret.filter(|s| !s.trim().is_empty()) {
+            return Ok(Key::new(&secret));
+        }
+        // a keyfile that was asked for has to be there, the default may be missing
+        let path = match keyfile {
+            Some(path) => path,
+            None if default.exists() => default.to_path_buf(),
+            None => {
+                return Err(InputError::NoKey {
+                    keyfile: default.to_path_buf(),
+                })
+            }
+        };
+
+        let secret = fs::read_to_string(&path).map_err(|e| InputError::Unreadable {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+        if secret.trim().is_empty() {
+            return Err(InputError::Unreadable {
+                path,
+                reason: "it is empty".to_string(),
+            });
+        }
+
+        return Ok(Key::new(&secret));
+    }
+
+    pub fn encrypt(&self, text: &str) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = self.0.encrypt(&nonce, text.as_bytes()).unwrap();
+
+        return [MAGIC, nonce.as_slice(), &sealed].concat();
+    }
+
+    pub fn decrypt(&self, bytes: &[u8]) -> Result<String, InputError> {
+        let bytes = bytes.strip_prefix(MAGIC).ok_or(InputError::WrongKey)?;
+        if bytes.len() < NONCE_LEN {
+            return Err(InputError::WrongKey);
+        }
+        let (nonce, sealed) = bytes.split_at(NONCE_LEN);
+        let text = self
+            .0
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .map_err(|_| InputError::WrongKey)?;
+
+        return String::from_utf8(text).map_err(|_| InputError::NotText);
+    }
+}
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    return bytes.starts_with(MAGIC);
+}
+
+// the text of an input, decrypted if it is encrypted; the key is only
+// looked for then, plain inputs like fixtures or a day that has none yet
+// need no key
+pub fn open_with(
+    bytes: &[u8],
+    key: impl FnOnce() -> Result<Key, InputError>,
+) -> Result<String, InputError> {
+    if !is_encrypted(bytes) {
+        return String::from_utf8(bytes.to_vec()).map_err(|_| InputError::NotText);
+    }
+    return key()?.decrypt(bytes);
+}
+
+// with the key of the repository at `root`
+pub fn open_in(root: &Path, bytes: &[u8]) -> Result<String, InputError> {
+    return open_with(bytes, || Key::load(root));
+}
+
+// what the days' binaries read their `include_bytes!`d input with
+pub fn open(bytes: &[u8]) -> Result<String, InputError> {
+    return open_in(root(), bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing() -> PathBuf {
+        return root().join("inputs").join("no-such-key");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::new("hunter2");
+        let sealed = key.encrypt("Time: 7 15 30\n");
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.windows(4).any(|w| w == b"Time"));
+        // the same secret gives the same key, whatever whitespace is around it
+        assert_eq!(
+            Key::new("hunter2\n").decrypt(&sealed),
+            Ok("Time: 7 15 30\n".to_string())
+        );
+        // a fresh nonce every time
+        assert_ne!(key.encrypt("Time: 7 15 30\n"), sealed);
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let sealed = Key::new("hunter2").encrypt("Time: 7 15 30\n");
+        assert_eq!(
+            Key::new("hunter3").decrypt(&sealed),
+            Err(InputError::WrongKey)
+        );
+
+        let mut damaged = sealed.clone();
+        *damaged.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            Key::new("hunter2").decrypt(&damaged),
+            Err(InputError::WrongKey)
+        );
+        assert_eq!(
+            Key::new("hunter2").decrypt(&sealed[..MAGIC.len() + 4]),
+            Err(InputError::WrongKey)
+        );
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let error = Key::find(None, None, &missing()).err().unwrap();
+        assert_eq!(error, InputError::NoKey { keyfile: missing() });
+        let message = error.to_string();
+        assert!(message.contains("there is no key"));
+        assert!(message.contains(KEY_VAR) && message.contains(KEYFILE_VAR));
+
+        // a keyfile that was asked for by name has to exist
+        assert!(matches!(
+            Key::find(None, Some(missing()), &missing()),
+            Err(InputError::Unreadable { .. })
+        ));
+        // an empty variable counts as unset
+        assert!(Key::find(Some(" ".to_string()), None, &missing()).is_err());
+    }
+
+    #[test]
+    fn test_key_sources() {
+        let sealed = Key::new("hunter2").encrypt("1abc2\n");
+        let keyfile = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+        // the variable wins over the file, which holds something else entirely
+        let key = Key::find(
+            Some("hunter2".to_string()),
+            Some(keyfile.clone()),
+            &missing(),
+        );
+        assert_eq!(key.unwrap().decrypt(&sealed), Ok("1abc2\n".to_string()));
+        let key = Key::find(None, Some(keyfile), &missing());
+        assert_eq!(key.unwrap().decrypt(&sealed), Err(InputError::WrongKey));
+    }
+
+    #[test]
+    fn test_open() {
+        let no_key = || Key::find(None, None, &missing());
+        assert_eq!(open_with(b"1abc2\n", no_key), Ok("1abc2\n".to_string()));
+        assert_eq!(open_with(b"", no_key), Ok(String::new()));
+        assert_eq!(open_with(&[0xff], no_key), Err(InputError::NotText));
+
+        let sealed = Key::new("hunter2").encrypt("1abc2\n");
+        assert!(matches!(
+            open_with(&sealed, no_key),
+            Err(InputError::NoKey { .. })
+        ));
+        assert_eq!(
+            open_with(&sealed, || Ok(Key::new("hunter2"))),
+            Ok("1abc2\n".to_string())
+        );
+    }
+}
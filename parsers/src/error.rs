@@ -0,0 +1,212 @@
+use std::fmt;
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+// where a puzzle input stopped making sense, and what should have been there
+#[derive(Clone, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    // 1-based, like an editor shows them
+    pub line: usize,
+    pub column: usize,
+    // the whole line the error is on
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    // `at` has to be a slice of `input`, usually what was left to parse
+    pub fn new(day: u8, input: &str, at: &str, expected: &str) -> ParseError {
+        let offset = offset(input, at);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+
+        return ParseError {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.to_string(),
+        };
+    }
+
+    // the same error, for a line that was parsed on its own
+    pub fn on_line(self, line: usize) -> ParseError {
+        return ParseError { line, ..self };
+    }
+
+    pub fn from_nom(day: u8, input: &str, error: nom::Err<VerboseError<&str>>) -> ParseError {
+        let error = match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                return ParseError::new(day, input, &input[input.len()..], "more input")
+            }
+        };
+
+        // the first entry is where parsing actually failed, the later ones
+        // are the parsers it was nested in
+        let at = error.errors.first().map(|(at, _)| *at).unwrap_or(input);
+        let expected = error
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                _ => None,
+            })
+            .or_else(|| error.errors.first().map(|(_, kind)| describe(kind)))
+            .unwrap_or("something else".to_string());
+
+        return ParseError::new(day, input, at, &expected);
+    }
+}
+
+// byte offset of `at` inside `input`, clamped to the end of it
+fn offset(input: &str, at: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let position = at.as_ptr() as usize;
+
+    if position < start || position > start + input.len() {
+        return input.len();
+    }
+    let mut offset = position - start;
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    return offset;
+}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    return match kind {
+        VerboseErrorKind::Context(context) => context.to_string(),
+        VerboseErrorKind::Char(c) => format!("{:?}", c),
+        VerboseErrorKind::Nom(ErrorKind::Digit) | VerboseErrorKind::Nom(ErrorKind::MapRes) => {
+            "a number".to_string()
+        }
+        VerboseErrorKind::Nom(ErrorKind::Alpha) => "a word".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => "the end of the line".to_string(),
+        VerboseErrorKind::Nom(ErrorKind::CrLf) => "a line break".to_string(),
+        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+    };
+}
+
+// error in day05 at line 3, column 4: expected a number
+//   |
+// 3 | 50 x8 2
+//   |    ^
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // keep the tabs so the caret lines up with the snippet
+        let padding = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+
+        writeln!(
+            f,
+            "error in day{:02} at line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, padding)
+    }
+}
+
+// the same as Display, so `main` returning a ParseError prints it readably
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{number, ws};
+    use nom::sequence::tuple;
+
+    const INPUT: &str = "seeds: 79 14\n\nseed-to-soil map:\n50 x8 2\n";
+
+    #[test]
+    fn test_new() {
+        let at = &INPUT[INPUT.find("x8").unwrap()..];
+        let error = ParseError::new(5, INPUT, at, "a number");
+        assert_eq!(
+            error,
+            ParseError {
+                day: 5,
+                line: 4,
+                column: 4,
+                snippet: "50 x8 2".to_string(),
+                expected: "a number".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_at_end() {
+        let error = ParseError::new(6, "Time: 7", &"Time: 7"[7..], "a line break");
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(error.snippet, "Time: 7");
+    }
+
+    #[test]
+    fn test_new_outside_input() {
+        // a slice from somewhere else is reported at the end of the input
+        let error = ParseError::new(1, "abc\ndef", "elsewhere", "a digit");
+        assert_eq!((error.line, error.column), (2, 4));
+    }
+
+    #[test]
+    fn test_crlf_and_wide_chars() {
+        let input = "ok\r\nαβ x\r\n";
+        let error = ParseError::new(1, input, &input[input.find('x').unwrap()..], "a digit");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.snippet, "αβ x");
+    }
+
+    #[test]
+    fn test_from_nom() {
+        let line = &INPUT[INPUT.find("50").unwrap()..];
+        let error = tuple((ws(number::<u64>), ws(number::<u64>)))(line).unwrap_err();
+        let error = ParseError::from_nom(5, INPUT, error);
+        assert_eq!((error.line, error.column), (4, 4));
+        assert_eq!(error.expected, "a number");
+    }
+
+    #[test]
+    fn test_display() {
+        let error = ParseError {
+            day: 5,
+            line: 12,
+            column: 4,
+            snippet: "50 x8 2".to_string(),
+            expected: "a number".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "error in day05 at line 12, column 4: expected a number
+   |
+12 | 50 x8 2
+   |    ^"
+        );
+    }
+
+    #[test]
+    fn test_display_keeps_tabs() {
+        let error = ParseError::new(4, "\tCard x", &"\tCard x"[6..], "a number");
+        assert!(error.to_string().ends_with("  | \t     ^"));
+    }
+}
@@ -0,0 +1,333 @@
+// nom combinators for the shapes the puzzle inputs keep coming in.
+//
+// they all follow the same whitespace rules: spaces and tabs around a token
+// belong to that token, so indented lines parse the same as flush ones, but
+// line endings are never consumed implicitly. Line endings can be "\n" or "\r\n".
+
+use std::str::FromStr;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        char, digit1, line_ending, multispace0, not_line_ending, one_of, space0, space1,
+    },
+    combinator::{all_consuming, cut, eof, map_res, not, opt, recognize, value},
+    error::{context, ErrorKind, ParseError as _, VerboseError},
+    multi::{many1, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    IResult, Parser,
+};
+
+mod error;
+mod stream;
+
+pub use error::ParseError;
+pub use stream::{for_each_line, ReadError};
+
+// what every parser here returns; the verbose errors keep enough of the trail
+// to point at the offending spot in the input, see `ParseError`
+pub type Parsed<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+// runs `inner` with the spaces and tabs around it removed
+pub fn ws<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> Parsed<'a, O>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    return delimited(space0, inner, space0);
+}
+
+// an integer of any type, with an optional sign
+pub fn number<T: FromStr>(input: &str) -> Parsed<'_, T> {
+    return context(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), |s: &str| {
+            s.parse::<T>()
+        }),
+    )(input);
+}
+
+// integers separated by any amount of spaces, possibly none at all
+pub fn numbers<T: FromStr>(input: &str) -> Parsed<'_, Vec<T>> {
+    return ws(separated_list0(space1, number))(input);
+}
+
+// `inner` after a fixed label, like the numbers after "Time:"
+pub fn labelled<'a, O, F>(label: &'static str, inner: F) -> impl FnMut(&'a str) -> Parsed<'a, O>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    return preceded(ws(context(label, tag(label))), ws(inner));
+}
+
+pub fn labelled_numbers<'a, T: FromStr>(
+    label: &'static str,
+) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    return labelled(label, numbers);
+}
+
+// a `key: value` record, like "Card 1: 41 48 83 | 83 86 6"
+pub fn record<'a, K, V, F, G>(key: F, value: G) -> impl FnMut(&'a str) -> Parsed<'a, (K, V)>
+where
+    F: Parser<&'a str, K, VerboseError<&'a str>>,
+    G: Parser<&'a str, V, VerboseError<&'a str>>,
+{
+    return separated_pair(ws(key), char(':'), ws(value));
+}
+
+// the end of a line, with any trailing spaces, or the end of the input
+pub fn line_end(input: &str) -> Parsed<'_, ()> {
+    return value((), preceded(space0, alt((line_ending, eof))))(input);
+}
+
+// the end of a line followed by at least one blank line; the blank lines may
+// hold indentation
+pub fn blank_lines(input: &str) -> Parsed<'_, ()> {
+    return value(
+        (),
+        pair(
+            preceded(space0, line_ending),
+            many1(preceded(space0, line_ending)),
+        ),
+    )(input);
+}
+
+// blocks of lines separated by blank lines, each parsed with `inner`; text
+// after blank lines has to be another block, so a broken one is reported
+// where it is instead of where the blocks stopped
+pub fn sections<'a, O, F>(inner: F) -> impl FnMut(&'a str) -> Parsed<'a, Vec<O>>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    let more = terminated(blank_lines, not(pair(multispace0, eof)));
+    return separated_list1(more, cut(inner));
+}
+
+// the rest of the line cut into cells of `width` characters, each trimmed;
+// the last cell may be shorter
+pub fn columns<'a>(width: usize) -> impl FnMut(&'a str) -> Parsed<'a, Vec<&'a str>> {
+    return move |input: &'a str| {
+        if width == 0 {
+            return Err(nom::Err::Failure(VerboseError::from_error_kind(
+                input,
+                ErrorKind::Verify,
+            )));
+        }
+
+        let (rest, line) = not_line_ending(input)?;
+        let mut cells = Vec::new();
+        let mut start = 0;
+        for (count, (i, _)) in line.char_indices().enumerate() {
+            if count > 0 && count % width == 0 {
+                cells.push(line[start..i].trim());
+                start = i;
+            }
+        }
+        if start < line.len() {
+            cells.push(line[start..].trim());
+        }
+
+        return Ok((rest, cells));
+    };
+}
+
+// numbers laid out in columns of `width` characters; empty cells are skipped
+pub fn column_numbers<'a, T: FromStr>(width: usize) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    let mut cells = columns(width);
+    return move |input: &'a str| {
+        let (rest, cells) = cells(input)?;
+        let numbers = cells
+            .iter()
+            .filter(|cell| !cell.is_empty())
+            .map(|cell| cell.parse::<T>())
+            .collect::<Result<Vec<T>, _>>()
+            .map_err(|_| nom::Err::Error(VerboseError::from_error_kind(input, ErrorKind::Digit)))?;
+
+        return Ok((rest, numbers));
+    };
+}
+
+// runs `parser` over the whole input, allowing for trailing blank lines
+pub fn parse_all<'a, O, F>(day: u8, input: &'a str, parser: F) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    return all_consuming(terminated(parser, multispace0))(input)
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::from_nom(day, input, e));
+}
+
+// runs `parser` over each line that is not blank; errors still point at the
+// line in the whole input
+pub fn parse_lines<'a, O, F>(day: u8, input: &'a str, mut parser: F) -> Result<Vec<O>, ParseError>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    return input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_line(day, i + 1, line, |l| parser.parse(l)))
+        .collect();
+}
+
+// runs `parser` over a single line, which is line `number` of the input
+pub fn parse_line<'a, O, F>(
+    day: u8,
+    number: usize,
+    line: &'a str,
+    parser: F,
+) -> Result<O, ParseError>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    return all_consuming(terminated(parser, space0))(line)
+        .map(|(_, output)| output)
+        .map_err(|e| ParseError::from_nom(day, line, e).on_line(number));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::character::complete::alpha1;
+    use nom::combinator::all_consuming;
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number::<u32>("42 rest"), Ok((" rest", 42)));
+        assert_eq!(number::<i64>("-17"), Ok(("", -17)));
+        assert_eq!(number::<u128>("+5"), Ok(("", 5)));
+        assert!(number::<u32>("-17").is_err());
+        assert!(number::<u8>("300").is_err());
+        assert!(number::<u32>("abc").is_err());
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            numbers::<u32>("1 2   3 4 5   "),
+            Ok(("", vec![1, 2, 3, 4, 5]))
+        );
+        assert_eq!(numbers::<u32>("\t 7\t8"), Ok(("", vec![7, 8])));
+        assert_eq!(numbers::<u32>("   "), Ok(("", vec![])));
+        assert_eq!(numbers::<u32>("1 2 | 3"), Ok(("| 3", vec![1, 2])));
+    }
+
+    #[test]
+    fn test_numbers_stop_at_line_end() {
+        assert_eq!(numbers::<u32>("1 2\n3 4"), Ok(("\n3 4", vec![1, 2])));
+        assert_eq!(numbers::<u32>("1 2 \r\n3"), Ok(("\r\n3", vec![1, 2])));
+    }
+
+    #[test]
+    fn test_labelled_numbers() {
+        let mut time = labelled_numbers::<u128>("Time:");
+        assert_eq!(time("Time:      7  15   30"), Ok(("", vec![7, 15, 30])));
+        assert_eq!(time("    Time: 7 15 30\n"), Ok(("\n", vec![7, 15, 30])));
+        assert!(time("Distance: 9 40 200").is_err());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut card = record(
+            labelled("Card", number::<u32>),
+            separated_pair(numbers::<u32>, char('|'), numbers::<u32>),
+        );
+        assert_eq!(
+            card("Card   3:  1 21 | 69 82  1"),
+            Ok(("", (3, (vec![1, 21], vec![69, 82, 1]))))
+        );
+
+        let mut entry = record(alpha1, number::<u32>);
+        assert_eq!(entry("  answer :42\r\n"), Ok(("\r\n", ("answer", 42))));
+    }
+
+    #[test]
+    fn test_line_end() {
+        assert_eq!(line_end("\nnext"), Ok(("next", ())));
+        assert_eq!(line_end("  \r\nnext"), Ok(("next", ())));
+        assert_eq!(line_end(""), Ok(("", ())));
+        assert!(line_end("x\n").is_err());
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        assert_eq!(blank_lines("\n\nnext"), Ok(("next", ())));
+        assert_eq!(blank_lines("\r\n\r\n\r\nnext"), Ok(("next", ())));
+        assert_eq!(blank_lines("\n    \n    next"), Ok(("    next", ())));
+        assert!(blank_lines("\nnext").is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        // unlike `numbers`, a line here needs at least one number, or the blank
+        // lines would be read as empty lines of a section
+        let line = ws(separated_list1(space1, number::<u32>));
+        let mut parser = all_consuming(sections(separated_list1(line_ending, line)));
+        let input = "1 2\n3\n\n4 5\r\n\r\n    6\n    \n7";
+        assert_eq!(
+            parser(input),
+            Ok((
+                "",
+                vec![
+                    vec![vec![1, 2], vec![3]],
+                    vec![vec![4, 5]],
+                    vec![vec![6]],
+                    vec![vec![7]]
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_sections_trailing_blank_lines() {
+        let line = ws(separated_list1(space1, number::<u32>));
+        let mut parser = sections(separated_list1(line_ending, line));
+        assert_eq!(
+            parser("1\n\n2\n\n  \n"),
+            Ok(("\n\n  \n", vec![vec![vec![1]], vec![vec![2]]]))
+        );
+        assert!(matches!(parser("1\n\nx"), Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(parse_all(6, "1 2 3\n\n", numbers::<u32>), Ok(vec![1, 2, 3]));
+
+        let error = parse_all(6, "1 2 x\n", numbers::<u32>).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (6, 1, 5));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "1 2\n\n3 4 \n  5\n";
+        assert_eq!(
+            parse_lines(4, input, numbers::<u32>),
+            Ok(vec![vec![1, 2], vec![3, 4], vec![5]])
+        );
+
+        let error = parse_lines(4, "1 2\n3 4\n5 x 6\n", numbers::<u32>).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.snippet, "5 x 6");
+    }
+
+    #[test]
+    fn test_columns() {
+        assert_eq!(
+            columns(3)(" 41 48  6 31\nnext"),
+            Ok(("\nnext", vec!["41", "48", "6", "31"]))
+        );
+        assert_eq!(columns(2)("ab cd"), Ok(("", vec!["ab", "c", "d"])));
+        assert_eq!(columns(4)(""), Ok(("", vec![])));
+        assert!(columns(0)("abc").is_err());
+    }
+
+    #[test]
+    fn test_column_numbers() {
+        assert_eq!(
+            column_numbers::<u32>(3)(" 83 86      31 17\r\n"),
+            Ok(("\r\n", vec![83, 86, 31, 17]))
+        );
+        assert!(column_numbers::<u32>(3)(" 83 xx").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// inputs read a line at a time, for the days whose lines stand on their own;
+// however long the input, only the current line is in memory
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::ParseError;
+
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> ReadError {
+        return ReadError::Io(error);
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(error: ParseError) -> ReadError {
+        return ReadError::Parse(error);
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "cannot read the input: {}", error),
+            ReadError::Parse(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+// the same as Display, like `ParseError`
+impl fmt::Debug for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+// calls `f` with every line of `reader` and its 1-based number, the line
+// ending taken off the way `str::lines` does it
+pub fn for_each_line<R, F>(mut reader: R, mut f: F) -> Result<(), ReadError>
+where
+    R: BufRead,
+    F: FnMut(usize, &str) -> Result<(), ParseError>,
+{
+    // one buffer for all the lines, it only grows to the longest of them
+    let mut buffer = String::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+
+        let line = match buffer.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => &buffer,
+        };
+        f(number, line)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{number, parse_line};
+
+    fn lines(input: &str) -> Vec<(usize, String)> {
+        let mut lines = Vec::new();
+        for_each_line(input.as_bytes(), |number, line| {
+            lines.push((number, line.to_string()));
+            return Ok(());
+        })
+        .unwrap();
+        return lines;
+    }
+
+    #[test]
+    fn test_for_each_line() {
+        for input in [
+            "a\n\nb\r\nc",
+            "a\n\nb\r\nc\n",
+            "a\r\n\r\nb\nc\r\n",
+            "x\ry\r",
+            "",
+        ] {
+            let expected = input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(lines(input), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_for_each_line_errors() {
+        let input = "1\n2\nx\n4";
+        let error = for_each_line(input.as_bytes(), |n, line| {
+            parse_line(9, n, line, number::<u32>)?;
+            return Ok(());
+        })
+        .unwrap_err();
+        match error {
+            ReadError::Parse(error) => {
+                assert_eq!((error.line, error.column), (3, 1));
+                assert_eq!(error.snippet, "x");
+            }
+            ReadError::Io(_) => panic!("expected a parse error"),
+        }
+
+        // a file that is not UTF-8
+        let error = for_each_line(&[b'1', b'\n', 0xff][..], |_, _| Ok(())).unwrap_err();
+        assert!(error.to_string().starts_with("cannot read the input"));
+    }
+}
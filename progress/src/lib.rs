@@ -0,0 +1,86 @@
+// what a long running solution and the runner share while it works: the
+// solution reports how far it got and checks whether it should give up, the
+// runner draws the progress and cancels once the time budget is spent.
+//
+// everything is atomic and relaxed, the numbers only feed a progress bar and
+// a cancelled solution merely has to notice at some point.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+pub struct Progress {
+    done: AtomicU64,
+    total: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    pub fn new() -> Progress {
+        return Progress::default();
+    }
+
+    // how many steps there are, in whatever unit the solution counts in
+    pub fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    pub fn advance(&self, steps: u64) {
+        self.done.fetch_add(steps, Ordering::Relaxed);
+    }
+
+    // the steps done so far and the total, which is 0 until a solution sets it
+    pub fn position(&self) -> (u64, u64) {
+        return (
+            self.done.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+        );
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_position() {
+        let progress = Progress::new();
+        assert_eq!(progress.position(), (0, 0));
+        progress.set_total(10);
+        progress.advance(3);
+        progress.advance(4);
+        assert_eq!(progress.position(), (7, 10));
+    }
+
+    #[test]
+    fn test_cancel_across_threads() {
+        let progress = Arc::new(Progress::new());
+        let worker = {
+            let progress = progress.clone();
+            thread::spawn(move || {
+                let mut steps = 0;
+                while !progress.is_cancelled() {
+                    progress.advance(1);
+                    steps += 1;
+                    thread::yield_now();
+                }
+                return steps;
+            })
+        };
+
+        while progress.position().0 < 10 {
+            thread::yield_now();
+        }
+        progress.cancel();
+        assert!(worker.join().unwrap() >= 10);
+    }
+}